@@ -0,0 +1,169 @@
+//! Generates code from the solutions in `src/bin/<year>-NN.rs` and the examples in `data/<year>/examples`:
+//!  1. the `days` module of the main binary, which includes every solution as a module, so they can be run
+//!     in-process (see `template::registry`).
+//!  2. one test per example of a day, included by `example_tests!` (see `template::examples`).
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    let bin_dir = manifest_dir.join("src").join("bin");
+    let data_dir = manifest_dir.join("data");
+
+    let puzzles: Vec<Puzzle> = list_files(&bin_dir, "rs")
+        .iter()
+        .filter_map(|stem| Puzzle::from_bin_name(stem))
+        .collect();
+
+    let mut years: Vec<&str> = puzzles.iter().map(|x| x.year.as_str()).collect();
+    years.dedup();
+
+    for year in years {
+        if data_dir.join(year).join("examples").exists() {
+            println!("cargo:rerun-if-changed=data/{year}/examples");
+        }
+    }
+
+    fs::write(out_dir.join("days.rs"), days_module(&bin_dir, &puzzles)).unwrap();
+
+    let tests_dir = out_dir.join("examples");
+    fs::create_dir_all(&tests_dir).unwrap();
+
+    for puzzle in &puzzles {
+        let examples_dir = data_dir.join(&puzzle.year).join("examples");
+        let tests = example_tests(&examples_dir, puzzle);
+        fs::write(tests_dir.join(format!("{}.rs", puzzle.bin_name())), tests).unwrap();
+    }
+}
+
+/// A solution in `src/bin`, named like `2024-01`.
+struct Puzzle {
+    year: String,
+    day: String,
+}
+
+impl Puzzle {
+    fn from_bin_name(stem: &str) -> Option<Self> {
+        let (year, day) = stem.split_once('-')?;
+
+        let valid_year = year.len() == 4 && year.parse::<u16>().is_ok_and(|x| x >= 2015);
+        let valid_day = day.len() == 2 && day.parse::<u8>().is_ok_and(|x| (1..=25).contains(&x));
+
+        (valid_year && valid_day).then(|| Puzzle {
+            year: year.to_string(),
+            day: day.to_string(),
+        })
+    }
+
+    fn bin_name(&self) -> String {
+        format!("{}-{}", self.year, self.day)
+    }
+
+    fn module_name(&self) -> String {
+        format!("day_{}_{}", self.year, self.day)
+    }
+}
+
+/// Sorted file stems of all files with an extension in a directory.
+fn list_files(dir: &Path, extension: &str) -> Vec<String> {
+    let mut stems: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|x| x == extension))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    stems.sort();
+    stems
+}
+
+fn days_module(bin_dir: &Path, puzzles: &[Puzzle]) -> String {
+    // NOTE: with `dhat-heap`, every solution defines a global allocator, so they cannot share a binary.
+    let puzzles = if env::var_os("CARGO_FEATURE_DHAT_HEAP").is_some() {
+        &[]
+    } else {
+        puzzles
+    };
+
+    let mut out = String::new();
+
+    for puzzle in puzzles {
+        let path = bin_dir.join(format!("{}.rs", puzzle.bin_name()));
+        writeln!(out, "#[cfg(not(test))]").unwrap();
+        writeln!(out, "#[allow(unused, clippy::all)]").unwrap();
+        writeln!(out, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(out, "mod {};", puzzle.module_name()).unwrap();
+    }
+
+    let solutions_type =
+        "&[(advent_of_code::template::Year, advent_of_code::template::registry::DaySolution)]";
+
+    // NOTE: solution tests already run as part of their own binaries.
+    writeln!(out, "#[cfg(not(test))]").unwrap();
+    writeln!(out, "pub const SOLUTIONS: {solutions_type} = &[").unwrap();
+    for puzzle in puzzles {
+        writeln!(
+            out,
+            "    (advent_of_code::template::Year::__new_unchecked({}), {}::SOLUTION),",
+            puzzle.year,
+            puzzle.module_name()
+        )
+        .unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    writeln!(out, "#[cfg(test)]").unwrap();
+    writeln!(out, "pub const SOLUTIONS: {solutions_type} = &[];").unwrap();
+
+    out
+}
+
+fn example_tests(examples_dir: &Path, puzzle: &Puzzle) -> String {
+    let day = &puzzle.day;
+    let mut names = vec![];
+
+    if examples_dir.join(format!("{day}.txt")).exists() {
+        // NOTE: keep in sync with `template::examples::DEFAULT_EXAMPLE`.
+        names.push(("example".to_string(), "example".to_string()));
+    }
+
+    for name in list_files(&examples_dir.join(day), "txt") {
+        let ident: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        names.push((format!("example_{ident}"), name));
+    }
+
+    let mut out = String::new();
+
+    for (ident, name) in names {
+        writeln!(out, "#[test]").unwrap();
+        writeln!(out, "fn {ident}() {{").unwrap();
+        writeln!(
+            out,
+            "    ::advent_of_code::template::examples::check(&super::SOLUTION, {name:?});"
+        )
+        .unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+
+    out
+}
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use advent_of_code::{CoordMap, Coords};
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(25, 1);
 
 fn is_matching(lock: &CoordMap, key: &CoordMap) -> bool {
     lock.iter().all(|(coord, letter)| {
@@ -47,10 +47,6 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(overlaps.len() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,10 +56,4 @@ mod tests {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(3));
     }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
 }
@@ -1,144 +1,1059 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, status, time, verify, watch,
+};
+use advent_of_code::template::{migrate, registry};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, Year};
 #[cfg(feature = "today")]
 use std::process;
 
+/// Solutions in `src/bin`, included as modules by `build.rs` so they can be run in-process.
+mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        commands::time::TimeOptions,
+        export::ExportFormat,
+        history,
+        input::InputSource,
+        parse_days,
+        report::OutputFormat,
+        runner::RunLimits,
+        solution_args::{parse_part, parse_seconds},
+        Day, DayFromStrError, Year,
+    };
+    use std::{collections::HashSet, ffi::OsString, path::PathBuf, str::FromStr};
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
+            refresh: bool,
         },
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+            overwrite: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+        },
+        Watch {
+            day: Day,
+            input: InputSource,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            format: Option<OutputFormat>,
+            input: InputSource,
         },
         All {
             release: bool,
+            limits: RunLimits,
+            jobs: usize,
+        },
+        Time(TimeOptions),
+        Verify {
+            release: bool,
+            pin: bool,
+            limits: RunLimits,
+            jobs: usize,
+        },
+        Status {
+            readme: bool,
         },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
+        Migrate {
+            year: Year,
+            dry_run: bool,
         },
+        Completions(Shell),
+        /// Print the help of the app or of a command.
+        Help(String),
         #[cfg(feature = "today")]
         Today,
     }
 
+    /* -------------------------------------------------------------------------- */
+
+    /// A flag or an option of a command, used for the help and the shell completions.
+    pub struct Flag {
+        pub name: &'static str,
+        /// Name of the value of an option, e.g. `<part>`. `None` for flags.
+        pub value: Option<&'static str>,
+        /// Values that shells offer for the option.
+        pub choices: &'static [&'static str],
+        pub help: &'static str,
+    }
+
+    const fn flag(name: &'static str, help: &'static str) -> Flag {
+        Flag {
+            name,
+            value: None,
+            choices: &[],
+            help,
+        }
+    }
+
+    const fn option(name: &'static str, value: &'static str, help: &'static str) -> Flag {
+        Flag {
+            name,
+            value: Some(value),
+            choices: &[],
+            help,
+        }
+    }
+
+    const fn choice(
+        name: &'static str,
+        value: &'static str,
+        choices: &'static [&'static str],
+        help: &'static str,
+    ) -> Flag {
+        Flag {
+            name,
+            value: Some(value),
+            choices,
+            help,
+        }
+    }
+
+    pub struct Command {
+        pub name: &'static str,
+        /// Positional argument, e.g. `<day>`.
+        pub positional: Option<&'static str>,
+        pub about: &'static str,
+        /// Groups of flags, so groups like the limits can be shared between commands.
+        pub flags: &'static [&'static [Flag]],
+    }
+
+    impl Command {
+        pub fn flags(&self) -> impl Iterator<Item = &Flag> {
+            self.flags.iter().flat_map(|x| x.iter())
+        }
+    }
+
+    pub const GLOBAL_FLAGS: &[Flag] = &[
+        option(
+            "--year",
+            "<year>",
+            "Year to work on, defaults to `AOC_YEAR` or the latest event",
+        ),
+        flag("--help", "Print help"),
+    ];
+
+    const RELEASE: &[Flag] = &[flag("--release", "Build solutions with optimizations")];
+
+    const LIMITS: &[Flag] = &[
+        option(
+            "--timeout",
+            "<seconds>",
+            "Stop a day after this many seconds",
+        ),
+        option(
+            "--part-timeout",
+            "<seconds>",
+            "Stop a part after this many seconds",
+        ),
+        option(
+            "--memory-limit",
+            "<mb>",
+            "Limit the memory of a solution, only on Linux",
+        ),
+    ];
+
+    const JOBS: &[Flag] = &[option("--jobs", "<n>", "Run this many days in parallel")];
+
+    const INPUT: &[Flag] = &[
+        flag(
+            "--example",
+            "Run on the example instead of the puzzle input",
+        ),
+        option("--input", "<path>", "Run on a file, or on stdin with `-`"),
+    ];
+
+    pub const COMMANDS: &[Command] = &[
+        Command {
+            name: "scaffold",
+            positional: Some("<day>"),
+            about: "Create the solution and data files of a day",
+            flags: &[&[
+                flag("--download", "Download the input and puzzle first"),
+                flag("--overwrite", "Replace an existing solution"),
+                option("--template", "<name>", "Use a template from `templates/`"),
+            ]],
+        },
+        Command {
+            name: "download",
+            positional: Some("<day>"),
+            about: "Download the input and puzzle of a day",
+            flags: &[&[
+                flag("--force", "Download the input and puzzle again"),
+                flag(
+                    "--refresh",
+                    "Download the puzzle again, e.g. for the second part",
+                ),
+            ]],
+        },
+        Command {
+            name: "read",
+            positional: Some("<day>"),
+            about: "Print the puzzle of a day",
+            flags: &[],
+        },
+        Command {
+            name: "examples",
+            positional: Some("<day>"),
+            about: "Extract the examples and their answers from the puzzle of a day",
+            flags: &[&[flag("--overwrite", "Replace existing examples")]],
+        },
+        Command {
+            name: "solve",
+            positional: Some("<day>"),
+            about: "Run the solution of a day",
+            flags: &[
+                RELEASE,
+                &[
+                    flag("--dhat", "Profile the heap with dhat"),
+                    choice(
+                        "--submit",
+                        "<part>",
+                        &["1", "2"],
+                        "Submit the answer of a part",
+                    ),
+                    choice("--format", "<format>", &["human", "json"], "Output format"),
+                ],
+                INPUT,
+            ],
+        },
+        Command {
+            name: "watch",
+            positional: Some("<day>"),
+            about: "Run the tests and the solution of a day whenever its files change",
+            flags: &[INPUT],
+        },
+        Command {
+            name: "all",
+            positional: None,
+            about: "Run the solutions of all days",
+            flags: &[RELEASE, LIMITS, JOBS],
+        },
+        Command {
+            name: "time",
+            positional: Some("[days]"),
+            about: "Benchmark the solutions of all days, or of days like `1-5,9`",
+            flags: &[
+                &[
+                    flag("--all", "Benchmark days that were benchmarked before"),
+                    flag("--store", "Store the timings and update the README"),
+                    flag("--median", "Show medians instead of means in the README"),
+                    flag("--compare", "Fail if a day regressed against the history"),
+                    option(
+                        "--threshold",
+                        "<percent>",
+                        "Regression threshold of `--compare`",
+                    ),
+                    option(
+                        "--baseline",
+                        "<name>",
+                        "Save the timings as a named baseline",
+                    ),
+                    option(
+                        "--against",
+                        "<name>",
+                        "Compare the timings with a named baseline",
+                    ),
+                    choice(
+                        "--export",
+                        "<format>",
+                        &["csv", "md", "html", "jsonl"],
+                        "Export the timings, needs `--out`",
+                    ),
+                    option("--out", "<path>", "File to export the timings to"),
+                ],
+                LIMITS,
+                JOBS,
+            ],
+        },
+        Command {
+            name: "verify",
+            positional: None,
+            about: "Check the answers of all days and their examples against the known answers",
+            flags: &[
+                RELEASE,
+                &[flag("--pin", "Store answers that are not known yet")],
+                LIMITS,
+                JOBS,
+            ],
+        },
+        Command {
+            name: "status",
+            positional: None,
+            about: "Show the progress of the event",
+            flags: &[&[flag("--readme", "Update the progress in the README")]],
+        },
+        Command {
+            name: "migrate",
+            positional: Some("<year>"),
+            about: "Move the files of the layout before multi-year support to a year",
+            flags: &[&[flag(
+                "--dry-run",
+                "Only print the files that would be moved",
+            )]],
+        },
+        Command {
+            name: "completions",
+            positional: Some("<shell>"),
+            about: "Print `cargo` completions of these commands for bash, zsh or fish",
+            flags: &[],
+        },
+        Command {
+            name: "today",
+            positional: None,
+            about: "Scaffold, download and read the puzzle of today, needs the `today` feature",
+            flags: &[],
+        },
+    ];
+
+    /// Shells that completions can be generated for.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Shell {
+        Bash,
+        Zsh,
+        Fish,
+    }
+
+    impl FromStr for Shell {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "bash" => Ok(Shell::Bash),
+                "zsh" => Ok(Shell::Zsh),
+                "fish" => Ok(Shell::Fish),
+                x => Err(format!(
+                    "unknown shell `{x}`, expecting `bash`, `zsh` or `fish`"
+                )),
+            }
+        }
+    }
+
+    /* -------------------------------------------------------------------------- */
+
+    fn format_flags<'a>(flags: impl Iterator<Item = &'a Flag>) -> Vec<String> {
+        let flags: Vec<(String, &str)> = flags
+            .map(|x| {
+                let name = match x.value {
+                    Some(value) => format!("{} {value}", x.name),
+                    None => x.name.to_string(),
+                };
+                (name, x.help)
+            })
+            .collect();
+
+        let width = flags.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+
+        flags
+            .iter()
+            .map(|(name, help)| format!("  {name:<width$}  {help}"))
+            .collect()
+    }
+
+    pub fn app_help() -> String {
+        let width = COMMANDS.iter().map(|x| x.name.len()).max().unwrap_or(0);
+
+        let mut lines = vec![
+            "Usage: cargo <command> [options]".to_string(),
+            String::new(),
+            "Commands:".to_string(),
+        ];
+
+        lines.extend(
+            COMMANDS
+                .iter()
+                .map(|x| format!("  {:<width$}  {}", x.name, x.about)),
+        );
+
+        lines.extend([String::new(), "Options:".to_string()]);
+        lines.extend(format_flags(GLOBAL_FLAGS.iter()));
+        lines.extend([
+            String::new(),
+            "Run `cargo <command> --help` for the options of a command.".to_string(),
+        ]);
+
+        lines.join("\n")
+    }
+
+    pub fn command_help(command: &Command) -> String {
+        let usage = match command.positional {
+            Some(positional) => format!("Usage: cargo {} {positional} [options]", command.name),
+            None => format!("Usage: cargo {} [options]", command.name),
+        };
+
+        let mut lines = vec![command.about.to_string(), String::new(), usage];
+        lines.extend([String::new(), "Options:".to_string()]);
+        lines.extend(format_flags(command.flags().chain(GLOBAL_FLAGS)));
+
+        lines.join("\n")
+    }
+
+    /* -------------------------------------------------------------------------- */
+
+    /// Parse a day of the current year's event.
+    fn parse_day(s: &str) -> Result<Day, String> {
+        let day: Day = s.parse().map_err(|e: DayFromStrError| e.to_string())?;
+        let year = Year::current();
+
+        if day > year.last_day() {
+            return Err(format!("the {year} event only has {} days", year.days()));
+        }
+
+        Ok(day)
+    }
+
+    /// Parse a list of days of the current year's event, e.g. `1-5,9`.
+    fn parse_day_list(s: &str) -> Result<HashSet<Day>, String> {
+        let days = parse_days(s)?;
+        let year = Year::current();
+
+        if days.last().is_some_and(|day| *day > year.last_day()) {
+            return Err(format!("the {year} event only has {} days", year.days()));
+        }
+
+        Ok(days.into_iter().collect())
+    }
+
+    fn parse_positive(s: &str) -> Result<f64, String> {
+        s.parse::<f64>()
+            .ok()
+            .filter(|x| x.is_finite() && *x > 0.0)
+            .ok_or_else(|| format!("expecting a positive number, got `{s}`"))
+    }
+
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<RunLimits, pico_args::Error> {
+        Ok(RunLimits {
+            day_timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
+            part_timeout: args.opt_value_from_fn("--part-timeout", parse_seconds)?,
+            memory_limit_mb: args.opt_value_from_str("--memory-limit")?,
+        })
+    }
+
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, pico_args::Error> {
+        let jobs = args.opt_value_from_fn("--jobs", |s: &str| {
+            s.parse::<usize>()
+                .ok()
+                .filter(|x| *x > 0)
+                .ok_or_else(|| format!("expecting a positive number of jobs, got `{s}`"))
+        })?;
+
+        Ok(jobs.unwrap_or(1))
+    }
+
+    fn parse_input(args: &mut pico_args::Arguments) -> Result<InputSource, String> {
+        let is_example = args.contains("--example");
+        let path: Option<String> = args
+            .opt_value_from_str("--input")
+            .map_err(|e| e.to_string())?;
+
+        InputSource::from_flags(is_example, path.as_deref())
+    }
+
+    fn parse_export(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<(ExportFormat, PathBuf)>, String> {
+        let format: Option<ExportFormat> = args
+            .opt_value_from_str("--export")
+            .map_err(|e| e.to_string())?;
+        let out: Option<PathBuf> = args
+            .opt_value_from_os_str("--out", |s| Ok::<_, String>(PathBuf::from(s)))
+            .map_err(|e| e.to_string())?;
+
+        match (format, out) {
+            (Some(format), Some(out)) => Ok(Some((format, out))),
+            (Some(_), None) => {
+                Err("`--export` needs a file to write to with `--out <file>`.".into())
+            }
+            (None, Some(_)) => Err("`--out` can only be used together with `--export`.".into()),
+            (None, None) => Ok(None),
+        }
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        parse_from(std::env::args_os().skip(1).collect())
+    }
+
+    fn parse_from(args: Vec<OsString>) -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let mut args = pico_args::Arguments::from_vec(args);
+
+        // The year applies to every command and is inherited by the solutions they run.
+        if let Some(year) = args.opt_value_from_str::<_, Year>("--year")? {
+            year.set_current();
+        }
+
+        let is_help = args.contains(["-h", "--help"]);
+        let subcommand = args.subcommand()?;
+
+        let command = match subcommand.as_deref() {
+            Some(name) => COMMANDS
+                .iter()
+                .find(|x| x.name == name)
+                .ok_or_else(|| format!("unknown command `{name}`.\n\n{}", app_help()))?,
+            None if is_help => return Ok(AppArguments::Help(app_help())),
+            None => return Err(format!("no command specified.\n\n{}", app_help()).into()),
+        };
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        if is_help {
+            return Ok(AppArguments::Help(command_help(command)));
+        }
+
+        // NOTE: positional arguments are read after all options, so they can be given in any order.
+        let app_args = match command.name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
+                limits: parse_limits(&mut args)?,
+                jobs: parse_jobs(&mut args)?,
             },
-            Some("time") => {
-                let all = args.contains("--all");
+            "time" => {
+                let run_all = args.contains("--all");
                 let store = args.contains("--store");
+                let median = args.contains("--median");
+                let limits = parse_limits(&mut args)?;
+                let jobs = parse_jobs(&mut args)?;
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_fn("--threshold", parse_positive)?;
+                let baseline = args.opt_value_from_str("--baseline")?;
+                let against = args.opt_value_from_str("--against")?;
+                let export = parse_export(&mut args)?;
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
+                AppArguments::Time(TimeOptions {
+                    days: args.opt_free_from_fn(parse_day_list)?,
+                    run_all,
                     store,
-                }
+                    median,
+                    limits,
+                    jobs,
+                    compare_threshold: compare
+                        .then(|| threshold.unwrap_or(history::DEFAULT_REGRESSION_THRESHOLD)),
+                    baseline,
+                    against,
+                    export,
+                })
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "verify" => AppArguments::Verify {
+                release: args.contains("--release"),
+                pin: args.contains("--pin"),
+                limits: parse_limits(&mut args)?,
+                jobs: parse_jobs(&mut args)?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+            "status" => AppArguments::Status {
+                readme: args.contains("--readme"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            "download" => {
+                let force = args.contains("--force");
+                let refresh = args.contains("--refresh");
+                AppArguments::Download {
+                    day: args.free_from_fn(parse_day)?,
+                    force,
+                    refresh,
+                }
+            }
+            "read" => AppArguments::Read {
+                day: args.free_from_fn(parse_day)?,
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "examples" => {
+                let overwrite = args.contains("--overwrite");
+                AppArguments::Examples {
+                    day: args.free_from_fn(parse_day)?,
+                    overwrite,
+                }
+            }
+            "scaffold" => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let template = args.opt_value_from_str("--template")?;
+                AppArguments::Scaffold {
+                    day: args.free_from_fn(parse_day)?,
+                    download,
+                    overwrite,
+                    template,
+                }
+            }
+            "watch" => {
+                let input = parse_input(&mut args)?;
+                AppArguments::Watch {
+                    day: args.free_from_fn(parse_day)?,
+                    input,
+                }
+            }
+            "solve" => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_fn("--submit", parse_part)?;
+                let dhat = args.contains("--dhat");
+                let format = args.opt_value_from_str("--format")?;
+                let input = parse_input(&mut args)?;
+                AppArguments::Solve {
+                    day: args.free_from_fn(parse_day)?,
+                    release,
+                    dhat,
+                    submit,
+                    format,
+                    input,
+                }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "migrate" => {
+                let dry_run = args.contains("--dry-run");
+                AppArguments::Migrate {
+                    year: args.free_from_str()?,
+                    dry_run,
+                }
             }
+            "completions" => AppArguments::Completions(args.free_from_str()?),
+            #[cfg(feature = "today")]
+            "today" => AppArguments::Today,
+            #[cfg(not(feature = "today"))]
+            "today" => {
+                return Err("`today` needs the `today` feature, run it with `cargo today`.".into())
+            }
+            _ => unreachable!("every command is handled"),
         };
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            return Err(format!(
+                "unknown argument(s) {remaining:?}. Run `cargo {} --help` for the options of the command.",
+                command.name
+            )
+            .into());
         }
 
         Ok(app_args)
     }
+
+    /* -------------------------------------------------------------------------- */
+
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::{parse_from, AppArguments, COMMANDS};
+        use advent_of_code::day;
+
+        fn parse(args: &[&str]) -> Result<AppArguments, String> {
+            parse_from(args.iter().map(Into::into).collect()).map_err(|e| e.to_string())
+        }
+
+        #[test]
+        fn parses_day_lists() {
+            let Ok(AppArguments::Time(options)) = parse(&["time", "--store", "1-3,9"]) else {
+                panic!("expected the time command");
+            };
+
+            let days = options.days.unwrap();
+            assert_eq!(days.len(), 4);
+            assert!(days.contains(&day!(9)));
+            assert!(options.store);
+        }
+
+        #[test]
+        fn validates_arguments() {
+            assert!(parse(&["solve", "1", "--submit", "3"]).is_err());
+            assert!(parse(&["solve", "1", "--bogus"]).is_err());
+            assert!(parse(&["solve", "26"]).is_err());
+            assert!(parse(&["solv", "1"]).is_err());
+            assert!(parse(&[]).is_err());
+            // NOTE: the year of the old layout has to be given explicitly.
+            assert!(parse(&["migrate"]).is_err());
+            assert!(matches!(
+                parse(&["migrate", "2023", "--dry-run"]),
+                Ok(AppArguments::Migrate { dry_run: true, .. })
+            ));
+            assert!(matches!(
+                parse(&["solve", "--release", "1", "--submit", "2"]),
+                Ok(AppArguments::Solve {
+                    submit: Some(2),
+                    release: true,
+                    ..
+                })
+            ));
+        }
+
+        #[test]
+        fn prints_help_of_every_command() {
+            for command in COMMANDS {
+                let Ok(AppArguments::Help(help)) = parse(&[command.name, "--help"]) else {
+                    panic!("expected help of `{}`", command.name);
+                };
+                assert!(help.contains(&format!("cargo {}", command.name)));
+                assert!(command.flags().all(|x| help.contains(x.name)));
+            }
+        }
+    }
 }
 
-fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
-        }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+/// Shell completions, generated from the command spec in [`args`].
+///
+/// The commands are run through their cargo aliases, so the completions are registered for `cargo`. Other cargo
+/// subcommands are left to cargo's own completions, if they are installed.
+mod completions {
+    use crate::args::{Command, Flag, Shell, COMMANDS, GLOBAL_FLAGS};
+
+    /// Name of the completion functions.
+    const NAME: &str = "advent_of_code";
+
+    pub fn generate(shell: Shell) -> String {
+        match shell {
+            Shell::Bash => bash(),
+            Shell::Zsh => zsh(),
+            Shell::Fish => fish(),
+        }
+    }
+
+    /// Whether the value of an option is a file.
+    fn is_path(flag: &Flag) -> bool {
+        flag.value == Some("<path>")
+    }
+
+    fn command_names() -> String {
+        COMMANDS
+            .iter()
+            .map(|x| x.name)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn flag_names<'a>(flags: impl Iterator<Item = &'a Flag>) -> String {
+        flags.map(|x| x.name).collect::<Vec<_>>().join(" ")
+    }
+
+    fn all_flags(command: &Command) -> impl Iterator<Item = &Flag> {
+        command.flags().chain(GLOBAL_FLAGS)
+    }
+
+    fn bash() -> String {
+        let mut options = vec![];
+        let mut cases = vec![];
+
+        for flag in COMMANDS.iter().flat_map(Command::flags).chain(GLOBAL_FLAGS) {
+            let Some(_) = flag.value else { continue };
+            let case = if is_path(flag) {
+                format!(
+                    "        {}) COMPREPLY=($(compgen -f -- \"$cur\")); return ;;",
+                    flag.name
+                )
+            } else {
+                format!(
+                    "        {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;",
+                    flag.name,
+                    flag.choices.join(" ")
+                )
+            };
+            if !options.contains(&case) {
+                options.push(case);
+            }
+        }
+
+        for command in COMMANDS {
+            let words = match command.positional {
+                Some("<shell>") => format!("bash zsh fish {}", flag_names(all_flags(command))),
+                _ => flag_names(all_flags(command)),
+            };
+            cases.push(format!(
+                "        {}) COMPREPLY=($(compgen -W \"{words}\" -- \"$cur\")) ;;",
+                command.name
+            ));
+        }
+
+        format!(
+            r#"_{NAME}_cargo() {{
+    if declare -F _cargo >/dev/null; then
+        _cargo "$@"
+    fi
+}}
+
+_{NAME}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local command="${{COMP_WORDS[1]}}"
+
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        _{NAME}_cargo "$@"
+        COMPREPLY+=($(compgen -W "{}" -- "$cur"))
+        return
+    fi
+
+    case "$command" in
+        {}) ;;
+        *) _{NAME}_cargo "$@"; return ;;
+    esac
+
+    case "$prev" in
+{}
+    esac
+
+    case "$command" in
+{}
+    esac
+}}
+
+# NOTE: load cargo's own completions first, so loading them lazily does not replace these.
+declare -F _completion_loader >/dev/null && _completion_loader cargo
+complete -F _{NAME} cargo
+"#,
+            command_names(),
+            command_names().replace(' ', "|"),
+            options.join("\n"),
+            cases.join("\n"),
+        )
+    }
+
+    /// Escape a description for `_arguments` and `_describe`.
+    fn zsh_escape(s: &str) -> String {
+        s.replace('\'', "'\\''")
+            .replace('[', "\\[")
+            .replace(']', "\\]")
+            .replace(':', "\\:")
+    }
+
+    fn zsh_flag(flag: &Flag) -> String {
+        let help = zsh_escape(flag.help);
+        match flag.value {
+            None => format!("'{}[{help}]'", flag.name),
+            Some(value) if is_path(flag) => format!("'{}[{help}]:{value}:_files'", flag.name),
+            Some(value) if flag.choices.is_empty() => format!("'{}[{help}]:{value}: '", flag.name),
+            Some(value) => format!(
+                "'{}[{help}]:{value}:({})'",
+                flag.name,
+                flag.choices.join(" ")
+            ),
+        }
+    }
+
+    fn zsh() -> String {
+        let commands: Vec<String> = COMMANDS
+            .iter()
+            .map(|x| format!("        '{}:{}'", x.name, zsh_escape(x.about)))
+            .collect();
+
+        let cases: Vec<String> = COMMANDS
+            .iter()
+            .map(|command| {
+                let mut specs: Vec<String> = all_flags(command).map(zsh_flag).collect();
+                if command.positional == Some("<shell>") {
+                    specs.push("'1:shell:(bash zsh fish)'".into());
                 }
+                format!(
+                    "        {})\n            _arguments {}\n            ;;",
+                    command.name,
+                    specs.join(" ")
+                )
+            })
+            .collect();
+
+        format!(
+            r#"#compdef cargo
+
+_{NAME}() {{
+    local -a commands=(
+{}
+    )
+    local -a names=({})
+
+    if (( CURRENT == 2 )); then
+        (( $+functions[_cargo] )) && _cargo
+        _describe 'command' commands
+        return
+    fi
+
+    if (( ! ${{names[(Ie)$words[2]]}} )); then
+        (( $+functions[_cargo] )) && _cargo
+        return
+    fi
+
+    # NOTE: drop `cargo`, so the command is the first word for `_arguments`.
+    words=("${{(@)words[2,-1]}}")
+    (( CURRENT-- ))
+
+    case $words[1] in
+{}
+    esac
+}}
+
+compdef _{NAME} cargo
+"#,
+            commands.join("\n"),
+            command_names(),
+            cases.join("\n"),
+        )
+    }
+
+    fn fish_escape(s: &str) -> String {
+        s.replace('\'', "\\'")
+    }
+
+    fn fish_flag(condition: &str, flag: &Flag) -> String {
+        let mut line = format!(
+            "complete -c cargo -n '{condition}' -l {} -d '{}'",
+            flag.name.trim_start_matches("--"),
+            fish_escape(flag.help)
+        );
+
+        match flag.value {
+            None => {}
+            Some(_) if is_path(flag) => line.push_str(" -r -F"),
+            Some(_) if flag.choices.is_empty() => line.push_str(" -x"),
+            Some(_) => line.push_str(&format!(" -x -a '{}'", flag.choices.join(" "))),
+        }
+
+        line
+    }
+
+    fn fish() -> String {
+        let any_command = format!("__fish_seen_subcommand_from {}", command_names());
+        let mut lines = vec![format!("complete -c cargo -n '{any_command}' -f")];
+
+        for flag in GLOBAL_FLAGS {
+            lines.push(fish_flag(&any_command, flag));
+        }
+
+        for command in COMMANDS {
+            lines.push(format!(
+                "complete -c cargo -n '__fish_use_subcommand' -a {} -d '{}'",
+                command.name,
+                fish_escape(command.about)
+            ));
+
+            let condition = format!("__fish_seen_subcommand_from {}", command.name);
+            for flag in command.flags() {
+                lines.push(fish_flag(&condition, flag));
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+
+            if command.positional == Some("<shell>") {
+                lines.push(format!(
+                    "complete -c cargo -n '{condition}' -a 'bash zsh fish'"
+                ));
             }
-        },
-    };
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    /* -------------------------------------------------------------------------- */
+
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::generate;
+        use crate::args::{Shell, COMMANDS};
+
+        #[test]
+        fn completes_every_command() {
+            for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+                let script = generate(shell);
+                for command in COMMANDS {
+                    assert!(
+                        script.contains(command.name),
+                        "{shell:?} misses {}",
+                        command.name
+                    );
+                }
+                assert!(script.contains("part-timeout"));
+                assert!(!script.contains("complete -c advent_of_code"));
+                assert!(
+                    script.contains("cargo"),
+                    "{shell:?} is not registered for cargo"
+                );
+            }
+        }
+    }
+}
+
+fn main() {
+    registry::register(days::SOLUTIONS);
+
+    let args = parse().unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    });
+
+    // NOTE: help and completions are printed as is, as they may be piped into a file.
+    if !matches!(
+        args,
+        AppArguments::Help(_) | AppArguments::Completions(_) | AppArguments::Migrate { .. }
+    ) {
+        migrate::warn_legacy();
+    }
+
+    match args {
+        AppArguments::All {
+            release,
+            limits,
+            jobs,
+        } => all::handle(release, limits, jobs),
+        AppArguments::Time(options) => time::handle(options),
+        AppArguments::Verify {
+            release,
+            pin,
+            limits,
+            jobs,
+        } => verify::handle(release, pin, limits, jobs),
+        AppArguments::Status { readme } => status::handle(readme),
+        AppArguments::Download {
+            day,
+            force,
+            refresh,
+        } => download::handle(day, force, refresh),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::Examples { day, overwrite } => examples::handle(day, overwrite),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+            template,
+        } => {
+            // NOTE: download first, so the template can use the title of the puzzle.
+            if download {
+                download::handle(day, false, false);
+            }
+            scaffold::handle(day, overwrite, template.as_deref());
+        }
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            format,
+            input,
+        } => solve::handle(day, release, dhat, submit, format, input),
+        AppArguments::Watch { day, input } => watch::handle(day, input),
+        AppArguments::Migrate { year, dry_run } => migrate::handle(year, dry_run),
+        AppArguments::Completions(shell) => print!("{}", completions::generate(shell)),
+        AppArguments::Help(help) => println!("{help}"),
+        #[cfg(feature = "today")]
+        AppArguments::Today => {
+            match Day::today() {
+                Some(day) => {
+                    Year::latest().set_current();
+                    download::handle(day, false, false);
+                    scaffold::handle(day, false, None);
+                    read::handle(day)
+                }
+                None => {
+                    eprintln!(
+                        "`today` command can only be run during the days of the event in \
+                            december. Please use `scaffold` with a specific day."
+                    );
+                    process::exit(1)
+                }
+            };
+        }
+    }
 }
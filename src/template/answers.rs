@@ -0,0 +1,297 @@
+/// Registry of known answers, stored as one JSON file per day in `data/<year>/answers`.
+///
+/// ```json
+/// { "input": { "part_1": "1234", "part_2": null }, "example": { "part_1": "11", "part_2": "31" }, "confirmed": [1] }
+/// ```
+///
+/// `confirmed` lists the parts whose input answer was accepted by the website. Answers pinned with
+/// `cargo verify --pin` stay unconfirmed.
+use std::{collections::HashMap, fs, io, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+fn answers_dir() -> PathBuf {
+    Year::current().data_dir().join("answers")
+}
+
+/// Which input an answer belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnswerKind {
+    Input,
+    Example,
+}
+
+impl AnswerKind {
+    fn key(self) -> &'static str {
+        match self {
+            AnswerKind::Input => "input",
+            AnswerKind::Example => "example",
+        }
+    }
+}
+
+/// Known answers for both parts of a single input.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartAnswers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl PartAnswers {
+    pub fn get(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: Option<String>) {
+        match part {
+            1 => self.part_1 = answer,
+            2 => self.part_2 = answer,
+            _ => {}
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Known answers of a day for both the real input and the example.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub input: PartAnswers,
+    pub example: PartAnswers,
+    /// Parts whose input answer was accepted by the website.
+    pub confirmed: Vec<u8>,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn get_path(day: Day) -> String {
+        answers_dir()
+            .join(format!("{day}.json"))
+            .display()
+            .to_string()
+    }
+
+    /// Read the known answers of a day. If no answers are stored, returns empty answers.
+    pub fn read_from_file(day: Day) -> Result<Self, String> {
+        match fs::read_to_string(Self::get_path(day)) {
+            Ok(content) => Answers::try_from(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Store the known answers of a day.
+    pub fn store_file(&self, day: Day) -> Result<(), io::Error> {
+        let dir = answers_dir();
+        if !dir.exists() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(Self::get_path(day))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, kind: AnswerKind) -> &PartAnswers {
+        match kind {
+            AnswerKind::Input => &self.input,
+            AnswerKind::Example => &self.example,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: AnswerKind) -> &mut PartAnswers {
+        match kind {
+            AnswerKind::Input => &mut self.input,
+            AnswerKind::Example => &mut self.example,
+        }
+    }
+
+    /// Whether the input answer of a part was accepted by the website.
+    pub fn is_confirmed(&self, part: u8) -> bool {
+        self.input.get(part).is_some() && self.confirmed.contains(&part)
+    }
+
+    /// Store the input answer of a part that the website accepted.
+    pub fn confirm(&mut self, part: u8, answer: &str) {
+        self.input.set(part, Some(answer.into()));
+        if !self.confirmed.contains(&part) {
+            self.confirmed.push(part);
+            self.confirmed.sort_unstable();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartAnswers> for JsonValue {
+    fn from(value: &PartAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let read_answer = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected answers.{key} to be null or string.")),
+        };
+
+        Ok(PartAnswers {
+            part_1: read_answer("part_1")?,
+            part_2: read_answer("part_2")?,
+        })
+    }
+}
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for kind in [AnswerKind::Input, AnswerKind::Example] {
+            map.insert(kind.key().into(), JsonValue::from(value.get(kind)));
+        }
+
+        map.insert(
+            "confirmed".into(),
+            JsonValue::Array(
+                value
+                    .confirmed
+                    .iter()
+                    .map(|part| JsonValue::Number(f64::from(*part)))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let read_answers = |kind: AnswerKind| match json.get(kind.key()) {
+            None => Ok(PartAnswers::default()),
+            Some(v) => PartAnswers::try_from(v),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let confirmed = match json.get("confirmed") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .and_then(|parts| {
+                    parts
+                        .iter()
+                        .map(|part| part.get::<f64>().map(|x| *x as u8))
+                        .collect::<Option<Vec<u8>>>()
+                })
+                .ok_or("Expected confirmed to be an array of part numbers.")?,
+        };
+
+        Ok(Answers {
+            input: read_answers(AnswerKind::Input)?,
+            example: read_answers(AnswerKind::Example)?,
+            confirmed,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AnswerKind, Answers, PartAnswers};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn handles_json_answers() {
+        let json =
+            r#"{ "input": { "part_1": "1234", "part_2": null }, "example": { "part_1": "11" } }"#
+                .to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.input.part_1, Some("1234".into()));
+        assert_eq!(answers.input.part_2, None);
+        assert_eq!(answers.get(AnswerKind::Example).get(1), Some(&"11".into()));
+        assert_eq!(answers.example.part_2, None);
+    }
+
+    #[test]
+    fn handles_missing_sections() {
+        let answers = Answers::try_from(r#"{ "input": { "part_2": "7" } }"#.to_string()).unwrap();
+        assert_eq!(answers.input.part_2, Some("7".into()));
+        assert_eq!(answers.example, PartAnswers::default());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        Answers::try_from(r#"{ "input": { "part_1": 1234 } }"#.to_string()).unwrap();
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.get_mut(AnswerKind::Input).set(1, Some("42".into()));
+        answers
+            .get_mut(AnswerKind::Example)
+            .set(2, Some("a\nb".into()));
+
+        answers.confirm(2, "7");
+
+        let json = JsonValue::from(answers.clone()).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn confirms_answers() {
+        let json = r#"{ "input": { "part_1": "1", "part_2": "2" }, "confirmed": [2] }"#.to_string();
+        let mut answers = Answers::try_from(json).unwrap();
+        assert!(!answers.is_confirmed(1));
+        assert!(answers.is_confirmed(2));
+
+        answers.confirm(1, "3");
+        assert_eq!(answers.input.part_1, Some("3".into()));
+        assert_eq!(answers.confirmed, vec![1, 2]);
+    }
+}
@@ -0,0 +1,344 @@
+/// Client for the Advent of Code website.
+///
+/// Requests are authenticated with the session cookie of a logged-in browser. It is read from the `AOC_SESSION`
+/// environment variable, or from a file: `session_file` in the `[client]` section of `aoc.toml`, defaulting to
+/// `~/.adventofcode.session` (the same file that `aoc-cli` uses).
+///
+/// The website can be swapped for a mock server by setting `AOC_BASE_URL` or `base_url` in `aoc.toml`.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{config::Config, html, Day, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was configured.
+    MissingSession,
+    /// The session cookie was rejected, e.g. because it expired.
+    Unauthorized,
+    /// The puzzle is not unlocked yet.
+    NotUnlocked,
+    /// Too many requests were made. Contains how long to wait before trying again, if known.
+    RateLimited(Option<Duration>),
+    /// The website answered with an unexpected status code.
+    BadStatus(u16),
+    /// The website could not be reached.
+    Network(String),
+    Io(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or store it in `~/{SESSION_FILE_NAME}`."
+            ),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected, it may have expired.")
+            }
+            AocClientError::NotUnlocked => write!(f, "the puzzle is not unlocked yet."),
+            AocClientError::RateLimited(Some(wait)) => write!(
+                f,
+                "too many requests, try again in {}s.",
+                wait.as_secs().max(1)
+            ),
+            AocClientError::RateLimited(None) => {
+                write!(f, "too many requests, try again later.")
+            }
+            AocClientError::BadStatus(status) => {
+                write!(f, "the website answered with status {status}.")
+            }
+            AocClientError::Network(e) => write!(f, "could not reach the website: {e}"),
+            AocClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            // NOTE: the website answers requests with a missing or invalid session cookie with a bad request.
+            // Server errors are kept as they are, as they say nothing about the cookie.
+            ureq::Error::Status(400 | 401 | 403, _) => AocClientError::Unauthorized,
+            ureq::Error::Status(404, _) => AocClientError::NotUnlocked,
+            ureq::Error::Status(429, response) => AocClientError::RateLimited(
+                response
+                    .header("Retry-After")
+                    .and_then(|x| x.trim().parse().ok())
+                    .map(Duration::from_secs),
+            ),
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Network(e.to_string()),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        AocClient {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.into(),
+            year,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(TIMEOUT)
+                .build(),
+        }
+    }
+
+    /// Create a client from the environment and `aoc.toml`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let config = Config::read_from_file().unwrap_or_else(|e| {
+            eprintln!("Warning: {e}");
+            Config::default()
+        });
+
+        let setting = |name: &str| {
+            config
+                .section("client")
+                .and_then(|x| x.get(name))
+                .and_then(|x| x.as_str())
+                .map(ToString::to_string)
+        };
+
+        let base_url = env::var("AOC_BASE_URL")
+            .ok()
+            .or_else(|| setting("base_url"))
+            .unwrap_or_else(|| DEFAULT_BASE_URL.into());
+
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => {
+                let path = setting("session_file")
+                    .map(PathBuf::from)
+                    .or_else(default_session_path)
+                    .ok_or(AocClientError::MissingSession)?;
+
+                match fs::read_to_string(path) {
+                    Ok(session) => session,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        return Err(AocClientError::MissingSession)
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        };
+
+        let session = session.trim();
+        if session.is_empty() {
+            return Err(AocClientError::MissingSession);
+        }
+
+        Ok(Self::new(&base_url, session, Year::current()))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self.agent.get(url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetch the puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetch the description of a day as Markdown. Includes part two once part one is solved.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let page = self.get(&self.day_url(day))?;
+        Ok(html::articles_to_markdown(&page, &self.base_url))
+    }
+
+    /// Submit the answer of a part and return the website's response as Markdown.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let page = response.into_string()?;
+        Ok(html::articles_to_markdown(&page, &self.base_url))
+    }
+}
+
+fn default_session_path() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(SESSION_FILE_NAME))
+}
+
+pub fn get_puzzle_path(day: Day) -> String {
+    Year::current()
+        .data_dir()
+        .join("puzzles")
+        .join(format!("{day}.md"))
+        .display()
+        .to_string()
+}
+
+pub fn get_input_path(day: Day) -> String {
+    Year::current()
+        .data_dir()
+        .join("inputs")
+        .join(format!("{day}.txt"))
+        .display()
+        .to_string()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError};
+    use crate::{day, template::Year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    /// Serve a single response on a local port. Returns its base URL and the request it received.
+    fn mock_server(status: &str, headers: &str, body: &str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(x) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = x.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (base_url, request) = mock_server("200 OK", "", "1 2\n3 4\n");
+        let client = AocClient::new(&base_url, "secret", Year::new(2024).unwrap());
+
+        assert_eq!(client.input(day!(1)).unwrap(), "1 2\n3 4\n");
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=secret"));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let (base_url, _) = mock_server(
+            "200 OK",
+            "",
+            "<main><article><h2>--- Day 3 ---</h2><p>Hi</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret", Year::new(2024).unwrap());
+        assert_eq!(client.puzzle(day!(3)).unwrap(), "## --- Day 3 ---\n\nHi");
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, request) = mock_server(
+            "200 OK",
+            "",
+            "<article><p>That's the right answer!</p></article>",
+        );
+        let client = AocClient::new(&base_url, "secret", Year::new(2023).unwrap());
+
+        assert_eq!(
+            client.submit(day!(2), 1, "42").unwrap(),
+            "That's the right answer!"
+        );
+
+        let request = request.recv().unwrap();
+        assert!(request.starts_with("POST /2023/day/2/answer HTTP/1.1"));
+        assert!(request.ends_with("level=1&answer=42"));
+    }
+
+    #[test]
+    fn maps_errors() {
+        let (base_url, _) = mock_server(
+            "404 Not Found",
+            "",
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        let client = AocClient::new(&base_url, "secret", Year::new(2024).unwrap());
+        assert!(matches!(
+            client.input(day!(25)),
+            Err(AocClientError::NotUnlocked)
+        ));
+
+        let (base_url, _) = mock_server("400 Bad Request", "", "Puzzle inputs differ by user.");
+        let client = AocClient::new(&base_url, "secret", Year::new(2024).unwrap());
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::Unauthorized)
+        ));
+
+        let (base_url, _) = mock_server("500 Internal Server Error", "", "");
+        let client = AocClient::new(&base_url, "secret", Year::new(2024).unwrap());
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::BadStatus(500))
+        ));
+
+        let (base_url, _) = mock_server("429 Too Many Requests", "Retry-After: 30\r\n", "");
+        let client = AocClient::new(&base_url, "secret", Year::new(2024).unwrap());
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::RateLimited(Some(x))) if x == Duration::from_secs(30)
+        ));
+    }
+}
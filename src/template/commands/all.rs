@@ -1,5 +1,12 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{all_days, input::InputSource, run_multi::run_multi, runner::RunLimits};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(is_release: bool, limits: RunLimits, jobs: usize) {
+    run_multi(
+        &all_days().collect(),
+        &InputSource::Input,
+        is_release,
+        false,
+        limits,
+        jobs,
+    );
 }
@@ -1,14 +1,106 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::{fs, io, path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+use crate::template::aoc_client::{get_input_path, get_puzzle_path, AocClient, AocClientError};
+use crate::template::manifest::{Integrity, Manifest};
+use crate::template::Day;
+
+fn read_non_empty(path: &str) -> Option<String> {
+    fs::read_to_string(path).ok().filter(|x| !x.is_empty())
+}
+
+/// Write a file, creating its directory if needed, e.g. for the first day of a new year.
+pub(crate) fn write_creating_dir(path: &str, content: &str) -> io::Result<()> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
     }
+    fs::write(path, content)
+}
+
+fn fetch(
+    client: &AocClient,
+    day: Day,
+    fetch_input: bool,
+    fetch_puzzle: bool,
+    manifest: &mut Manifest,
+) -> Result<(), AocClientError> {
+    if fetch_input {
+        let input = client.input(day)?;
+        write_creating_dir(&get_input_path(day), &input)?;
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+        manifest.record(day, &input);
+        if let Err(e) = manifest.store_file() {
+            eprintln!("Failed to store input manifest: {e}");
+        }
+    }
+
+    if fetch_puzzle {
+        refresh_puzzle(client, day)?;
+    }
+
+    Ok(())
+}
+
+/// Fetch the puzzle description of a day again, e.g. once a part was solved and the next one unlocked.
+pub fn refresh_puzzle(client: &AocClient, day: Day) -> Result<(), AocClientError> {
+    let puzzle = client.puzzle(day)?;
+    write_creating_dir(&get_puzzle_path(day), &(puzzle + "\n"))?;
+    Ok(())
+}
+
+/// Download the input and puzzle description of a day.
+///
+/// Non-empty files on disk are used as a cache. With `force`, both are fetched again, with `refresh` only the
+/// puzzle description, e.g. to read the second part. The description is also refreshed when `--submit`
+/// gets an answer accepted.
+pub fn handle(day: Day, force: bool, refresh: bool) {
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+
+    let mut manifest = Manifest::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read input manifest: {e}");
         process::exit(1);
-    };
+    });
+
+    let cached_input = read_non_empty(&input_path).filter(|_| !force);
+    let cached_puzzle = read_non_empty(&puzzle_path).filter(|_| !force && !refresh);
+
+    if cached_input.is_none() || cached_puzzle.is_none() {
+        let client = AocClient::from_env().unwrap_or_else(|e| {
+            eprintln!("Failed to set up the Advent of Code client: {e}");
+            process::exit(1);
+        });
+
+        let result = fetch(
+            &client,
+            day,
+            cached_input.is_none(),
+            cached_puzzle.is_none(),
+            &mut manifest,
+        );
+
+        if let Err(e) = result {
+            eprintln!("Failed to download day {day}: {e}");
+            process::exit(1);
+        }
+    }
+
+    println!("---");
+
+    match cached_input {
+        Some(input) => {
+            println!("🎄 Using cached input \"{}\".", &input_path);
+            if manifest.check(day, &input) == Integrity::Modified {
+                eprintln!(
+                    "Warning: \"{input_path}\" was edited after it was downloaded. Run `cargo download {day} --force` to restore it."
+                );
+            }
+        }
+        None => println!("🎄 Successfully wrote input to \"{}\".", &input_path),
+    }
+
+    if cached_puzzle.is_some() {
+        println!("🎄 Using cached puzzle \"{}\".", &puzzle_path);
+    } else {
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    }
 }
@@ -0,0 +1,235 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::answers::{AnswerKind, Answers, PartAnswers};
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::examples::{self, ExtractedExample};
+use crate::template::{get_path_for_bin, Day, PuzzleId, Year};
+
+static PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+/// Write the input of an example unless a different, non-empty one exists. Returns whether the example on
+/// disk is the extracted one.
+fn write_input(path: &Path, input: &str, overwrite: bool) -> io::Result<bool> {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing.trim_end() == input => return Ok(true),
+        Ok(existing) if !existing.is_empty() && !overwrite => return Ok(false),
+        _ => {}
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, input)?;
+    Ok(true)
+}
+
+/// Add extracted answers to the known ones. Known answers are only replaced with `overwrite`.
+fn merge(known: &mut PartAnswers, extracted: &PartAnswers, overwrite: bool) {
+    for part in [1, 2] {
+        if let Some(answer) = extracted.get(part) {
+            if overwrite || known.get(part).is_none() {
+                known.set(part, Some(answer.clone()));
+            }
+        }
+    }
+}
+
+/// Path of the default example, see [`examples::DEFAULT_EXAMPLE`].
+fn default_path(day: Day) -> PathBuf {
+    Year::current()
+        .data_dir()
+        .join("examples")
+        .join(format!("{day}.txt"))
+}
+
+fn store_default(
+    day: Day,
+    path: &Path,
+    example: &ExtractedExample,
+    overwrite: bool,
+) -> Result<bool, String> {
+    if !write_input(path, &example.input, overwrite).map_err(|e| e.to_string())? {
+        return Ok(false);
+    }
+
+    let mut answers = Answers::read_from_file(day)?;
+    merge(
+        answers.get_mut(AnswerKind::Example),
+        &example.answers,
+        overwrite,
+    );
+    answers.store_file(day).map_err(|e| e.to_string())?;
+
+    Ok(true)
+}
+
+fn store_named(path: &Path, example: &ExtractedExample, overwrite: bool) -> Result<bool, String> {
+    if !write_input(path, &example.input, overwrite).map_err(|e| e.to_string())? {
+        return Ok(false);
+    }
+
+    let sidecar = path.with_extension("json");
+    let mut answers = match fs::read_to_string(&sidecar) {
+        Ok(content) => JsonValue::from_str(&content)
+            .map_err(|_| format!("`{}` is not a valid JSON file.", sidecar.display()))
+            .and_then(|json| PartAnswers::try_from(&json))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => PartAnswers::default(),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    merge(&mut answers, &example.answers, overwrite);
+
+    let mut file = fs::File::create(&sidecar).map_err(|e| e.to_string())?;
+    JsonValue::from(&answers)
+        .format_to(&mut file)
+        .map_err(|e| e.to_string())?;
+
+    Ok(true)
+}
+
+fn to_literal(answer: &str) -> String {
+    if answer.parse::<i128>().is_ok() {
+        answer.into()
+    } else {
+        format!("{answer:?}.to_string()")
+    }
+}
+
+/// Fill the `assert_eq!(result, None);` placeholders that solutions scaffolded by older versions of the
+/// template have in their example tests. Returns the parts that were filled.
+fn fill_placeholders(source: &mut String, answers: &PartAnswers) -> Vec<u8> {
+    let mut filled = vec![];
+
+    for (part, name) in [(1, "test_part_one"), (2, "test_part_two")] {
+        let Some(answer) = answers.get(part) else {
+            continue;
+        };
+
+        let Some(start) = source.find(&format!("fn {name}()")) else {
+            continue;
+        };
+        let end = source[start + 3..]
+            .find("fn ")
+            .map_or(source.len(), |x| start + 3 + x);
+
+        let body = &source[start..end];
+        if !body.contains("read_file(\"examples\", DAY)") {
+            continue;
+        }
+
+        if let Some(pos) = body.find(PLACEHOLDER) {
+            source.replace_range(
+                start + pos..start + pos + PLACEHOLDER.len(),
+                &format!("assert_eq!(result, Some({}));", to_literal(answer)),
+            );
+            filled.push(part);
+        }
+    }
+
+    filled
+}
+
+fn format_answers(answers: &PartAnswers) -> String {
+    [1, 2]
+        .map(|part| format!("part {part}: {}", answers.get(part).map_or("-", |x| x)))
+        .join(", ")
+}
+
+pub fn handle(day: Day, overwrite: bool) {
+    let puzzle_path = get_puzzle_path(day);
+    let markdown = fs::read_to_string(&puzzle_path).unwrap_or_else(|e| {
+        eprintln!("Failed to read \"{puzzle_path}\": {e}. Run `cargo download {day}` first.");
+        process::exit(1);
+    });
+
+    let extracted = examples::extract(&markdown);
+    if extracted.is_empty() {
+        println!("No examples found in \"{puzzle_path}\".");
+        return;
+    }
+
+    for (i, example) in extracted.iter().enumerate() {
+        let (path, result) = if i == 0 {
+            let path = default_path(day);
+            let result = store_default(day, &path, example, overwrite);
+            (path, result)
+        } else {
+            let path = examples::get_dir(day).join(format!("{}.txt", i + 1));
+            let result = store_named(&path, example, overwrite);
+            (path, result)
+        };
+        let label = path.display();
+
+        match result {
+            Ok(true) => println!(
+                "Wrote example \"{label}\" ({})",
+                format_answers(&example.answers)
+            ),
+            Ok(false) => println!(
+                "Kept existing example \"{label}\", it differs from the puzzle. Use `--overwrite` to replace it."
+            ),
+            Err(e) => {
+                eprintln!("Failed to store example \"{label}\": {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let module_path = get_path_for_bin(day);
+    if let Ok(mut source) = fs::read_to_string(&module_path) {
+        let filled = fill_placeholders(&mut source, &extracted[0].answers);
+        if !filled.is_empty() {
+            if let Err(e) = fs::write(&module_path, source) {
+                eprintln!("Failed to fill in expected answers in \"{module_path}\": {e}");
+                process::exit(1);
+            }
+            println!("Filled in expected answers of part(s) {filled:?} in \"{module_path}\"");
+        }
+    }
+
+    println!("---");
+    println!(
+        "🎄 Type `cargo test --bin {}` to check your solution against the examples.",
+        PuzzleId::from(day)
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::fill_placeholders;
+    use crate::template::answers::PartAnswers;
+
+    #[test]
+    fn fills_placeholders() {
+        let mut source = [
+            "fn test_part_one() {",
+            "    let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));",
+            "    assert_eq!(result, None);",
+            "}",
+            "fn test_part_two() {",
+            "    let result = part_two(&advent_of_code::template::read_file(\"examples\", DAY));",
+            "    assert_eq!(result, None);",
+            "}",
+        ]
+        .join("\n");
+
+        let answers = PartAnswers {
+            part_1: Some("11".into()),
+            part_2: Some("a,b".into()),
+        };
+
+        assert_eq!(fill_placeholders(&mut source, &answers), vec![1, 2]);
+        assert!(source.contains("assert_eq!(result, Some(11));"));
+        assert!(source.contains("assert_eq!(result, Some(\"a,b\".to_string()));"));
+        assert!(fill_placeholders(&mut source, &answers).is_empty());
+    }
+}
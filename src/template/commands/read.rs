@@ -1,15 +1,23 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::{get_puzzle_path, AocClient};
+use crate::template::commands::download::write_creating_dir;
+use crate::template::Day;
 
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let client = AocClient::from_env().unwrap_or_else(|e| {
+        eprintln!("Failed to set up the Advent of Code client: {e}");
         process::exit(1);
-    }
+    });
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let puzzle = client.puzzle(day).unwrap_or_else(|e| {
+        eprintln!("Failed to read day {day}: {e}");
         process::exit(1);
-    };
+    });
+
+    println!("{puzzle}");
+
+    if let Err(e) = write_creating_dir(&get_puzzle_path(day), &(puzzle + "\n")) {
+        eprintln!("Failed to store puzzle: {e}");
+    }
 }
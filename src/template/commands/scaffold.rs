@@ -1,14 +1,119 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::answers::{AnswerKind, Answers, PartAnswers};
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::diff::{diff_lines, format_diff};
+use crate::template::{get_path_for_bin, Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Directory of the named templates, e.g. `templates/grid.txt` for `--template grid`.
+static TEMPLATES_DIR: &str = "templates";
+
+/// Value of variables that are not known yet, e.g. the title of a puzzle that was not downloaded.
+static UNKNOWN: &str = "?";
+
+/// Values of the variables that templates can use.
+struct TemplateVars {
+    day: Day,
+    year: Year,
+    title: Option<String>,
+    example_answers: PartAnswers,
+}
+
+impl TemplateVars {
+    /// Collect the variables of a day from the downloaded puzzle and the known example answers.
+    fn read(day: Day) -> Self {
+        let title = fs::read_to_string(get_puzzle_path(day))
+            .ok()
+            .and_then(|markdown| puzzle_title(&markdown).map(String::from));
+
+        let example_answers = Answers::read_from_file(day)
+            .map(|answers| answers.get(AnswerKind::Example).clone())
+            .unwrap_or_default();
+
+        TemplateVars {
+            day,
+            year: Year::current(),
+            title,
+            example_answers,
+        }
+    }
+
+    fn render(&self, template: &str) -> String {
+        let answer = |part| {
+            self.example_answers
+                .get(part)
+                .map_or(UNKNOWN.into(), String::clone)
+        };
+
+        [
+            ("%DAY_NUMBER%", self.day.into_inner().to_string()),
+            ("%DAY%", self.day.to_string()),
+            ("%YEAR%", self.year.to_string()),
+            ("%TITLE%", self.title.clone().unwrap_or(UNKNOWN.into())),
+            ("%EXAMPLE_PART_ONE%", answer(1)),
+            ("%EXAMPLE_PART_TWO%", answer(2)),
+        ]
+        .iter()
+        .fold(template.to_string(), |acc, (name, value)| {
+            acc.replace(name, value)
+        })
+    }
+}
+
+/// Title of a downloaded puzzle, from its `--- Day 1: Historian Hysteria ---` heading.
+fn puzzle_title(markdown: &str) -> Option<&str> {
+    markdown.lines().find_map(|line| {
+        let heading = line
+            .trim_start_matches('#')
+            .trim()
+            .strip_prefix("--- Day ")?;
+        let (_, title) = heading.split_once(": ")?;
+        title.strip_suffix(" ---")
+    })
+}
+
+/// Names of the templates in `templates/`.
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|x| x == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort();
+    names
+}
+
+/// Read a named template from `templates/`, or the built-in one without a name.
+fn read_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(MODULE_TEMPLATE.into());
+    };
+
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(format!(
+            "unknown template `{name}`, available templates: {}",
+            available_templates().join(", ")
+        )),
+        Err(e) => Err(format!("failed to read \"{}\": {e}", path.display())),
+    }
+}
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -19,18 +124,47 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Create an empty file unless it exists, so downloaded inputs and examples are never wiped.
+/// Returns whether the file was created.
+fn create_empty_file(path: &Path) -> Result<bool, std::io::Error> {
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Create the solution of a day from a template, along with empty input and example files.
+///
+/// With `overwrite`, an existing solution is replaced and the changes are printed as a diff.
+pub fn handle(day: Day, overwrite: bool, template: Option<&str>) {
+    let template = read_template(template).unwrap_or_else(|e| {
+        eprintln!("Failed to read template: {e}");
+        process::exit(1);
+    });
+
+    let data_dir = Year::current().data_dir();
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let example_path = data_dir.join("examples").join(format!("{day}.txt"));
+    let module_path = get_path_for_bin(day);
+
+    for dir in ["inputs", "examples"] {
+        if let Err(e) = fs::create_dir_all(data_dir.join(dir)) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
+
+    let content = TemplateVars::read(day).render(&template);
+
+    if overwrite {
+        if let Ok(existing) = fs::read_to_string(&module_path) {
+            match format_diff(&diff_lines(&existing, &content)) {
+                Some(diff) => println!("Replacing \"{module_path}\":\n{diff}"),
+                None => println!("\"{module_path}\" is already up to date."),
+            }
+        }
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +174,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(content.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -54,9 +184,12 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    match create_empty_file(&input_path) {
+        Ok(true) => {
+            println!("Created empty input file \"{}\"", input_path.display());
+        }
+        Ok(false) => {
+            println!("Kept existing input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -64,9 +197,12 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    match create_empty_file(&example_path) {
+        Ok(true) => {
+            println!("Created empty example file \"{}\"", example_path.display());
+        }
+        Ok(false) => {
+            println!("Kept existing example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -77,3 +213,40 @@ pub fn handle(day: Day, overwrite: bool) {
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{puzzle_title, TemplateVars};
+    use crate::{
+        day,
+        template::{answers::PartAnswers, Year},
+    };
+
+    #[test]
+    fn reads_puzzle_titles() {
+        let markdown = "## --- Day 1: Historian Hysteria ---\n\nThe *Chief Historian*.\n";
+        assert_eq!(puzzle_title(markdown), Some("Historian Hysteria"));
+        assert_eq!(puzzle_title("## --- Part Two ---"), None);
+    }
+
+    #[test]
+    fn renders_variables() {
+        let vars = TemplateVars {
+            day: day!(8),
+            year: Year::new(2023).unwrap(),
+            title: Some("Haunted Wasteland".into()),
+            example_answers: PartAnswers {
+                part_1: Some("6".into()),
+                part_2: None,
+            },
+        };
+
+        assert_eq!(
+            vars.render("solution!(%DAY_NUMBER%); // %YEAR%-%DAY%: %TITLE%"),
+            "solution!(8); // 2023-08: Haunted Wasteland"
+        );
+        assert_eq!(vars.render("%EXAMPLE_PART_ONE% %EXAMPLE_PART_TWO%"), "6 ?");
+    }
+}
@@ -1,9 +1,48 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{
+    input::InputSource, report::OutputFormat, solution_args::SolutionArgs, Day, PuzzleId,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    format: Option<OutputFormat>,
+    input: InputSource,
+) {
+    if submit_part.is_some() && input != InputSource::Input {
+        eprintln!("`--submit` can only be used with the puzzle input.");
+        process::exit(1);
+    }
+
+    let cmd_args = cargo_args(day, release, dhat, submit_part, format, &input);
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
+
+/// Arguments of the `cargo run` invocation that solves a day.
+pub(crate) fn cargo_args(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    format: Option<OutputFormat>,
+    input: &InputSource,
+) -> Vec<String> {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        PuzzleId::from(day).to_string(),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -18,17 +57,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+    let solution_args = SolutionArgs {
+        submit: submit_part,
+        format: format.unwrap_or_default(),
+        input: input.clone(),
+        ..SolutionArgs::default()
+    };
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
-
-    cmd.wait().unwrap();
+    cmd_args.extend(solution_args.to_args());
+    cmd_args
 }
@@ -0,0 +1,54 @@
+use std::process;
+
+use crate::template::history::History;
+use crate::template::manifest::{Integrity, Manifest};
+use crate::template::progress::{self, DayProgress};
+use crate::template::readme_benchmarks::Error;
+use crate::template::timings::Timings;
+use crate::template::{all_days, ANSI_ITALIC, ANSI_RESET};
+
+pub fn handle(update_readme: bool) {
+    let history = History::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read benchmark history: {e}");
+        process::exit(1);
+    });
+    let timings = Timings::read_from_file();
+
+    let days: Vec<DayProgress> = all_days()
+        .map(|day| DayProgress::read(day, &history, &timings))
+        .collect();
+
+    println!("{}", progress::render_terminal(&days));
+    println!(
+        "{ANSI_ITALIC}bin: scaffolded, in: input, ex: example, time: latest benchmark{ANSI_RESET}"
+    );
+
+    match Manifest::read_from_file() {
+        Ok(manifest) => {
+            for day in all_days().filter(|day| manifest.check_file(*day) == Integrity::Modified) {
+                eprintln!(
+                    "Warning: the input of day {day} was edited after it was downloaded. Run `cargo download {day} --force` to restore it."
+                );
+            }
+        }
+        Err(e) => eprintln!("Failed to read input manifest: {e}"),
+    }
+
+    if update_readme {
+        println!();
+        match progress::update_readme(&days) {
+            Ok(()) => println!("Updated progress in README."),
+            Err(Error::Parser(_)) => {
+                eprintln!(
+                    "Failed to update progress in README: add two `{}` comments where it should go.",
+                    progress::MARKER
+                );
+                process::exit(1);
+            }
+            Err(Error::IO(e)) => {
+                eprintln!("Failed to update progress in README: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
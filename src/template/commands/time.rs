@@ -1,34 +1,138 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process;
 
+use crate::template::export::{self, ExportFormat};
+use crate::template::history::{self, Comparison, History, HistoryEntry};
+use crate::template::input::InputSource;
+use crate::template::readme_benchmarks::TimingMetric;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::runner::RunLimits;
+use crate::template::timings::{format_nanos, PartTiming, Timing, Timings};
+use crate::template::{
+    all_days, readme_benchmarks, registry, runner::part_label, Day, ANSI_BOLD, ANSI_GREEN,
+    ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
+
+/// Profile that benchmarks are built with.
+const PROFILE: &str = "release";
+
+/// Options of the `time` command.
+#[derive(Clone, Debug)]
+pub struct TimeOptions {
+    /// Only bench these days.
+    pub days: Option<HashSet<Day>>,
+    /// Bench all days, including fully benched ones.
+    pub run_all: bool,
+    /// Store the timings and update the README.
+    pub store: bool,
+    /// Show medians instead of means in the README.
+    pub median: bool,
+    pub limits: RunLimits,
+    pub jobs: usize,
+    /// Compare with the benchmark history, failing if a day regressed by more than this many percent.
+    pub compare_threshold: Option<f64>,
+    /// Save the timings to a named baseline.
+    pub baseline: Option<String>,
+    /// Diff the timings against a named baseline.
+    pub against: Option<String>,
+    /// Export the stored timings, merged with this run, to a file.
+    pub export: Option<(ExportFormat, PathBuf)>,
+}
+
+pub fn handle(options: TimeOptions) {
+    let TimeOptions {
+        days,
+        run_all,
+        store,
+        median,
+        limits,
+        jobs,
+        compare_threshold,
+        baseline,
+        against,
+        export,
+    } = options;
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
+    let against_timings = against
+        .as_ref()
+        .map(|name| match Timings::read_baseline(name) {
+            Ok(timings) if timings.data.is_empty() => {
+                eprintln!("No baseline named `{name}` found.");
+                process::exit(1);
             }
-        },
-        |day| HashSet::from([day]),
-    );
+            Ok(timings) => timings,
+            Err(e) => {
+                eprintln!("Failed to read baseline `{name}`: {e}");
+                process::exit(1);
+            }
+        });
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // NOTE: validate the name before benching, so a typo does not throw away a run.
+    if let Some(Err(e)) = baseline.as_deref().map(Timings::get_baseline_path) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    let history = History::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read benchmark history: {e}");
+        process::exit(1);
+    });
+
+    let days_to_run = days.unwrap_or_else(|| {
+        // NOTE: comparing needs fresh timings of every day, including fully benched ones.
+        if run_all || compare_threshold.is_some() || against.is_some() {
+            all_days().collect()
+        } else {
+            // when the `--all` flag is not set, filter out days that are fully benched.
+            all_days()
+                .filter(|day| !stored_timings.is_day_complete(*day, registry::parts(*day)))
+                .collect()
+        }
+    });
+
+    if jobs > 1 {
+        // NOTE: benchmarks stay sequential by default; concurrent days compete for CPU and skew timings.
+        eprintln!(
+            "{ANSI_ITALIC}Note: benchmarking {jobs} days in parallel, timings may be less accurate.{ANSI_RESET}"
+        );
+    }
+
+    let timings = run_multi(&days_to_run, &InputSource::Input, true, true, limits, jobs)
+        .timings
+        .unwrap();
+
+    // NOTE: compare before appending the run, so a regressed run is marked and never becomes a baseline.
+    let comparisons = compare_threshold.map(|threshold| {
+        let comparisons = history::compare(&history, &days_to_run, &timings, PROFILE);
+        let is_regressed = comparisons.iter().any(|x| x.is_regression(threshold));
+        (threshold, comparisons, is_regressed)
+    });
+
+    if !timings.data.is_empty() {
+        let mut entry = HistoryEntry::new(timings.clone(), PROFILE);
+        entry.regressed = comparisons.as_ref().is_some_and(|(_, _, x)| *x);
+
+        if let Err(e) = History::append_file(&entry) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(
+            merged_timings,
+            if median {
+                TimingMetric::Median
+            } else {
+                TimingMetric::Mean
+            },
+        ) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +141,249 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if let Some(name) = &baseline {
+        let merged_timings = Timings::read_baseline(name)
+            .unwrap_or_default()
+            .merge(&timings);
+
+        match merged_timings.store_baseline(name) {
+            Ok(()) => println!("Stored baseline `{name}`."),
+            Err(e) => eprintln!("Failed to store baseline `{name}`: {e}"),
+        }
+    }
+
+    if let Some((format, path)) = &export {
+        match export::write(*format, &stored_timings.merge(&timings), path) {
+            Ok(()) => println!("Exported benchmarks to `{}`.", path.display()),
+            Err(e) => eprintln!("Failed to export benchmarks to `{}`: {e}", path.display()),
+        }
+    }
+
+    if let (Some(name), Some(old)) = (&against, &against_timings) {
+        print_diff(name, &diff_timings(old, &timings));
+    }
+
+    if let Some((threshold, comparisons, is_regressed)) = comparisons {
+        print_comparisons(&comparisons, threshold);
+
+        if is_regressed {
+            process::exit(1);
+        }
+    }
+}
+
+fn print_comparisons(comparisons: &[Comparison], threshold: f64) {
+    println!();
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET} (regression threshold: {threshold}%)");
+    println!("------");
+
+    if comparisons.is_empty() {
+        println!("{ANSI_ITALIC}No previous runs to compare with.{ANSI_RESET}");
+        return;
+    }
+
+    for comparison in comparisons {
+        let since = comparison
+            .baseline_commit
+            .as_ref()
+            .map(|x| format!(", since {x}"))
+            .unwrap_or_default();
+
+        if comparison.is_broken() {
+            let parts = comparison
+                .broken_parts
+                .iter()
+                .map(|part| part_label(*part))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            println!(
+                "Day {}: {} → ✖ broken ({parts} not solved{since})",
+                comparison.day,
+                format_nanos(comparison.baseline_nanos),
+            );
+            continue;
+        }
+
+        let verdict = if comparison.is_regression(threshold) {
+            " ✖ regressed"
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {}: {} → {} ({:+.1}%{since}){verdict}",
+            comparison.day,
+            format_nanos(comparison.baseline_nanos),
+            format_nanos(comparison.current_nanos),
+            comparison.change_percent()
+        );
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|x| x.is_regression(threshold))
+        .count();
+
+    println!();
+    println!("{ANSI_BOLD}{regressions} regressed{ANSI_RESET}");
+}
+
+/// Median of a part in a baseline and in the current run.
+#[derive(Clone, Debug, PartialEq)]
+struct PartDiff {
+    day: Day,
+    part: u8,
+    old_nanos: Option<f64>,
+    new_nanos: Option<f64>,
+}
+
+fn diff_timings(old: &Timings, new: &Timings) -> Vec<PartDiff> {
+    let median = |timing: Option<&Timing>, part: u8| {
+        timing
+            .and_then(|t| t.part(part))
+            .filter(|p| p.is_solved())
+            .map(PartTiming::median_nanos)
+    };
+
+    new.data
+        .iter()
+        .flat_map(|timing| {
+            let old = old.data.iter().find(|t| t.day == timing.day);
+
+            [0, 1, 2].into_iter().filter_map(move |part| {
+                let (old_nanos, new_nanos) = (median(old, part), median(Some(timing), part));
+
+                (old_nanos.is_some() || new_nanos.is_some()).then_some(PartDiff {
+                    day: timing.day,
+                    part,
+                    old_nanos,
+                    new_nanos,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Percentage change with an arrow, red if the part got slower and green if it got faster.
+pub(crate) fn format_change(old_nanos: Option<f64>, new_nanos: Option<f64>) -> String {
+    match (old_nanos, new_nanos) {
+        (Some(old), Some(new)) if old > 0.0 => {
+            let change = (new - old) / old * 100.0;
+
+            if change > 0.0 {
+                format!("{ANSI_RED}↑ {change:+.1}%{ANSI_RESET}")
+            } else if change < 0.0 {
+                format!("{ANSI_GREEN}↓ {change:+.1}%{ANSI_RESET}")
+            } else {
+                "= 0.0%".into()
+            }
+        }
+        _ => "-".into(),
+    }
+}
+
+fn print_diff(name: &str, diffs: &[PartDiff]) {
+    let cell = |nanos: Option<f64>| nanos.map_or_else(|| "-".into(), format_nanos);
+
+    println!();
+    println!("{ANSI_BOLD}Against `{name}`{ANSI_RESET}");
+    println!("------");
+    println!(
+        "{:<8}{:<8}{:>12}{:>12}  Change",
+        "Day", "Part", "Old", "New"
+    );
+
+    for diff in diffs {
+        println!(
+            "{:<8}{:<8}{:>12}{:>12}  {}",
+            format!("Day {}", diff.day),
+            part_label(diff.part),
+            cell(diff.old_nanos),
+            cell(diff.new_nanos),
+            format_change(diff.old_nanos, diff.new_nanos)
+        );
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{diff_timings, format_change, PartDiff};
+    use crate::{
+        day,
+        template::{
+            report::PartStatus,
+            stats::BenchStats,
+            timings::{PartTiming, Timing, Timings},
+            Day,
+        },
+    };
+
+    fn part(median_nanos: f64) -> PartTiming {
+        PartTiming {
+            status: PartStatus::Solved,
+            nanos: 1e6,
+            samples: 1,
+            stats: Some(BenchStats {
+                median_nanos,
+                ..BenchStats::default()
+            }),
+        }
+    }
+
+    fn timing(day: Day, part_1: Option<f64>, part_2: Option<f64>) -> Timing {
+        Timing {
+            day,
+            parse: None,
+            part_1: part_1.map(part),
+            part_2: part_2.map(part),
+            total_nanos: 0.0,
+        }
+    }
+
+    #[test]
+    fn diffs_parts_of_each_day() {
+        let old = Timings {
+            data: vec![timing(day!(1), Some(100.0), None)],
+        };
+        let new = Timings {
+            data: vec![
+                timing(day!(1), Some(80.0), Some(50.0)),
+                timing(day!(2), Some(10.0), None),
+            ],
+        };
+
+        assert_eq!(
+            diff_timings(&old, &new),
+            vec![
+                PartDiff {
+                    day: day!(1),
+                    part: 1,
+                    old_nanos: Some(100.0),
+                    new_nanos: Some(80.0)
+                },
+                PartDiff {
+                    day: day!(1),
+                    part: 2,
+                    old_nanos: None,
+                    new_nanos: Some(50.0)
+                },
+                PartDiff {
+                    day: day!(2),
+                    part: 1,
+                    old_nanos: None,
+                    new_nanos: Some(10.0)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_changes() {
+        assert!(format_change(Some(100.0), Some(80.0)).contains("↓ -20.0%"));
+        assert!(format_change(Some(100.0), Some(125.0)).contains("↑ +25.0%"));
+        assert_eq!(format_change(Some(100.0), Some(100.0)), "= 0.0%");
+        assert_eq!(format_change(None, Some(100.0)), "-");
+    }
 }
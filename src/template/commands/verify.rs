@@ -0,0 +1,307 @@
+use std::collections::HashSet;
+use std::process;
+
+use crate::template::answers::{AnswerKind, Answers};
+use crate::template::input::InputSource;
+use crate::template::report::{PartReport, PartStatus};
+use crate::template::run_multi::{run_multi, MultiRun};
+use crate::template::runner::RunLimits;
+use crate::template::submissions::Submissions;
+use crate::template::{all_days, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Outcome of comparing a part's result with its known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// The part was solved, but no answer is known for it.
+    Missing,
+}
+
+impl Verdict {
+    fn cell(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "✔ pass",
+            Verdict::Fail { .. } => "✖ fail",
+            Verdict::Missing => "? missing",
+        }
+    }
+}
+
+/// Compare a part's report with its known answer.
+/// Returns `None` if there is neither a known answer nor a solution for the part.
+pub fn verify_part(expected: Option<&String>, report: Option<&PartReport>) -> Option<Verdict> {
+    let actual = report
+        .filter(|r| r.status == PartStatus::Solved)
+        .and_then(|r| r.answer.clone());
+
+    match (expected, actual) {
+        (None, None) => None,
+        (None, Some(_)) => Some(Verdict::Missing),
+        (Some(expected), Some(actual)) if *expected == actual => Some(Verdict::Pass),
+        (Some(expected), actual) => Some(Verdict::Fail {
+            expected: expected.clone(),
+            actual,
+        }),
+    }
+}
+
+/// Verdicts of both parts of a day on its puzzle input or on its example.
+type Row = (Day, AnswerKind, [Option<Verdict>; 2]);
+
+fn find_reports(run: &MultiRun, day: Day) -> &[PartReport] {
+    run.reports
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, reports)| reports.as_slice())
+        .unwrap_or_default()
+}
+
+pub fn handle(is_release: bool, pin: bool, limits: RunLimits, jobs: usize) {
+    let mut days: Vec<(Day, Answers)> = vec![];
+
+    for day in all_days() {
+        match Answers::read_from_file(day) {
+            Ok(answers) => days.push((day, answers)),
+            Err(e) => {
+                eprintln!("Failed to read answers for day {day}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let run = run_multi(
+        &all_days().collect(),
+        &InputSource::Input,
+        is_release,
+        false,
+        limits,
+        jobs,
+    );
+
+    // NOTE: only days with expected example answers run on their example, others would have nothing to check.
+    let example_days: HashSet<Day> = days
+        .iter()
+        .filter(|(_, answers)| !answers.get(AnswerKind::Example).is_empty())
+        .map(|(day, _)| *day)
+        .collect();
+
+    let example_run = if example_days.is_empty() {
+        None
+    } else {
+        println!();
+        println!("{ANSI_BOLD}Examples{ANSI_RESET}");
+        Some(run_multi(
+            &example_days,
+            &InputSource::Example,
+            is_release,
+            false,
+            limits,
+            jobs,
+        ))
+    };
+
+    let mut rows: Vec<Row> = vec![];
+
+    for (day, mut answers) in days {
+        let submissions = Submissions::read_from_file(day).unwrap_or_else(|e| {
+            eprintln!("Failed to read submissions for day {day}: {e}");
+            Submissions::default()
+        });
+
+        let reports = find_reports(&run, day);
+
+        if !answers.is_empty() || !reports.is_empty() {
+            let verdicts = [1, 2].map(|part| {
+                // NOTE: answers accepted by the website take precedence over pinned ones, which may be wrong.
+                let expected = submissions
+                    .correct_answer(part)
+                    .or_else(|| answers.get(AnswerKind::Input).get(part));
+                let report = reports.iter().find(|r| r.part == part);
+                (part, report, verify_part(expected, report))
+            });
+
+            if pin {
+                let mut changed = false;
+
+                for (part, report, verdict) in &verdicts {
+                    if *verdict == Some(Verdict::Missing) {
+                        let answer = report.and_then(|r| r.answer.clone());
+                        answers.get_mut(AnswerKind::Input).set(*part, answer);
+                        // NOTE: pinned answers were never checked by the website.
+                        answers.confirmed.retain(|x| x != part);
+                        changed = true;
+                    }
+                }
+
+                if changed {
+                    if let Err(e) = answers.store_file(day) {
+                        eprintln!("Failed to store answers for day {day}: {e}");
+                        process::exit(1);
+                    }
+                }
+            }
+
+            rows.push((
+                day,
+                AnswerKind::Input,
+                verdicts.map(|(_, _, verdict)| verdict),
+            ));
+        }
+
+        if let Some(example_run) = example_run.as_ref().filter(|_| example_days.contains(&day)) {
+            let reports = find_reports(example_run, day);
+            let expected = answers.get(AnswerKind::Example);
+            // NOTE: parts without an expected example answer are not checked, like in the example tests.
+            let verdicts = [1, 2].map(|part| {
+                let report = reports.iter().find(|r| r.part == part);
+                expected
+                    .get(part)
+                    .and_then(|expected| verify_part(Some(expected), report))
+            });
+            rows.push((day, AnswerKind::Example, verdicts));
+        }
+    }
+
+    print_matrix(&rows, pin);
+
+    let failed = rows
+        .iter()
+        .flat_map(|(_, _, verdicts)| verdicts.iter())
+        .any(|v| matches!(v, Some(Verdict::Fail { .. })));
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn row_label(day: Day, kind: AnswerKind) -> String {
+    match kind {
+        AnswerKind::Input => format!("Day {day}"),
+        AnswerKind::Example => format!("Day {day} example"),
+    }
+}
+
+fn print_matrix(rows: &[Row], pin: bool) {
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+    println!("{:<16}{:<12}{:<12}", "Day", "Part 1", "Part 2");
+
+    for (day, kind, verdicts) in rows {
+        let [part_1, part_2] = verdicts
+            .each_ref()
+            .map(|v| v.as_ref().map_or("-", Verdict::cell));
+        println!("{:<16}{part_1:<12}{part_2:<12}", row_label(*day, *kind));
+    }
+
+    let mut failures = vec![];
+    let (mut passed, mut missing) = (0, 0);
+
+    for (day, kind, verdicts) in rows {
+        for (i, verdict) in verdicts.iter().enumerate() {
+            match verdict {
+                Some(Verdict::Pass) => passed += 1,
+                Some(Verdict::Missing) => missing += 1,
+                Some(Verdict::Fail { expected, actual }) => failures.push(format!(
+                    "{}, Part {}: expected `{expected}`, got `{}`",
+                    row_label(*day, *kind),
+                    i + 1,
+                    actual.as_deref().unwrap_or("✖")
+                )),
+                None => {}
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!();
+        for failure in &failures {
+            println!("✖ {failure}");
+        }
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}{passed} passed, {} failed, {missing} missing{ANSI_RESET}",
+        failures.len()
+    );
+
+    if pin && missing > 0 {
+        println!(
+            "{ANSI_ITALIC}Pinned {missing} missing answers to \"{}\". They are unconfirmed until the website \
+            accepts them, and do not earn stars.{ANSI_RESET}",
+            Year::current().data_dir().join("answers").display()
+        );
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verify_part, Verdict};
+    use crate::{
+        day,
+        template::{
+            report::{PartReport, PartStatus},
+            stats::BenchStats,
+        },
+    };
+
+    fn report(answer: Option<&str>, status: PartStatus) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part: 1,
+            answer: answer.map(Into::into),
+            status,
+            error: None,
+            stats: BenchStats::default(),
+        }
+    }
+
+    #[test]
+    fn passes_matching_answers() {
+        let r = report(Some("42"), PartStatus::Solved);
+        assert_eq!(
+            verify_part(Some(&"42".into()), Some(&r)),
+            Some(Verdict::Pass)
+        );
+    }
+
+    #[test]
+    fn fails_mismatching_answers() {
+        let r = report(Some("41"), PartStatus::Solved);
+        assert_eq!(
+            verify_part(Some(&"42".into()), Some(&r)),
+            Some(Verdict::Fail {
+                expected: "42".into(),
+                actual: Some("41".into())
+            })
+        );
+    }
+
+    #[test]
+    fn fails_unsolved_parts_with_known_answers() {
+        let r = report(None, PartStatus::Panicked);
+        assert_eq!(
+            verify_part(Some(&"42".into()), Some(&r)),
+            Some(Verdict::Fail {
+                expected: "42".into(),
+                actual: None
+            })
+        );
+        assert!(matches!(
+            verify_part(Some(&"42".into()), None),
+            Some(Verdict::Fail { .. })
+        ));
+    }
+
+    #[test]
+    fn flags_missing_answers() {
+        let r = report(Some("42"), PartStatus::Solved);
+        assert_eq!(verify_part(None, Some(&r)), Some(Verdict::Missing));
+        assert_eq!(verify_part(None, None), None);
+    }
+}
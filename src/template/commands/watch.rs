@@ -0,0 +1,273 @@
+use std::{
+    fs,
+    path::PathBuf,
+    process::{self, Command},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::commands::{solve, time::format_change};
+use crate::template::input::InputSource;
+use crate::template::report::{OutputFormat, PartReport, Record};
+use crate::template::runner::format_report;
+use crate::template::{
+    examples, get_path_for_bin, Day, PuzzleId, Year, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED,
+    ANSI_RESET,
+};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Clears the terminal and moves the cursor to the top left.
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Lines of failed test output shown below the results.
+const TEST_OUTPUT_LINES: usize = 30;
+
+/// Files whose changes trigger a new run: the solution, the shared library and the day's data.
+/// The named examples are listed anew on every call, so added and removed examples are noticed.
+fn watched_paths(day: Day, input: &InputSource) -> Vec<PathBuf> {
+    let data_dir = Year::current().data_dir();
+
+    let mut paths = vec![
+        PathBuf::from(get_path_for_bin(day)),
+        PathBuf::from("src/lib.rs"),
+        data_dir.join("inputs").join(format!("{day}.txt")),
+        data_dir.join("examples").join(format!("{day}.txt")),
+    ];
+
+    if let InputSource::Path(path) = input {
+        paths.push(path.clone());
+    }
+
+    let mut examples: Vec<PathBuf> = fs::read_dir(examples::get_dir(day))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default();
+    examples.sort();
+    paths.extend(examples);
+
+    paths
+}
+
+/// Every watched file with its modification time, `None` for files that do not exist.
+fn snapshot(paths: Vec<PathBuf>) -> Vec<(PathBuf, Option<SystemTime>)> {
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|x| x.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Result of a single run of the tests and the solution.
+struct RunOutcome {
+    tests_passed: bool,
+    /// Output of the tests, only kept if they failed.
+    test_output: Vec<String>,
+    reports: Vec<PartReport>,
+    /// Output of the solution that is not a report, e.g. debug prints or compile errors.
+    output: Vec<String>,
+}
+
+fn run(day: Day, input: &InputSource) -> RunOutcome {
+    let bin_name = PuzzleId::from(day).to_string();
+
+    let tests = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &bin_name])
+        .output();
+
+    let (tests_passed, test_output) = match tests {
+        Ok(output) if output.status.success() => (true, vec![]),
+        Ok(output) => (
+            false,
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .chain(String::from_utf8_lossy(&output.stderr).lines())
+                .map(String::from)
+                .collect(),
+        ),
+        Err(e) => (false, vec![format!("Failed to run tests: {e}")]),
+    };
+
+    let mut args = solve::cargo_args(day, true, false, None, Some(OutputFormat::Json), input);
+    args.insert(1, "--quiet".into());
+
+    let mut reports = vec![];
+    let mut output = vec![];
+
+    match Command::new("cargo").args(&args).output() {
+        Ok(result) => {
+            for line in String::from_utf8_lossy(&result.stdout).lines() {
+                let (text, record) = Record::split_line(line);
+                if record.is_none() || !text.is_empty() {
+                    output.push(text.to_string());
+                }
+                if let Some(Record::Report(report)) = record {
+                    reports.push(report);
+                }
+            }
+            output.extend(
+                String::from_utf8_lossy(&result.stderr)
+                    .lines()
+                    .map(String::from),
+            );
+        }
+        Err(e) => output.push(format!("Failed to run solution: {e}")),
+    }
+
+    RunOutcome {
+        tests_passed,
+        test_output,
+        reports,
+        output,
+    }
+}
+
+fn mean_nanos(reports: &[PartReport], part: u8) -> Option<f64> {
+    reports
+        .iter()
+        .find(|x| x.part == part)
+        .map(|x| x.stats.mean_nanos)
+}
+
+/// Compact header with the outcome of the tests, followed by every part with its change in time since the
+/// previous run.
+fn format_results(
+    day: Day,
+    run_count: usize,
+    outcome: &RunOutcome,
+    previous: &[PartReport],
+) -> Vec<String> {
+    let tests = if outcome.tests_passed {
+        format!("{ANSI_GREEN}✔ tests passed{ANSI_RESET}")
+    } else {
+        format!("{ANSI_RED}✖ tests failed{ANSI_RESET}")
+    };
+
+    let mut lines = vec![
+        format!(
+            "{ANSI_BOLD}Day {day}{ANSI_RESET} | {tests} | {ANSI_ITALIC}run #{run_count}{ANSI_RESET}"
+        ),
+        String::new(),
+    ];
+
+    for report in &outcome.reports {
+        lines.push(format!(
+            "{}  {}",
+            format_report(report),
+            format_change(
+                mean_nanos(previous, report.part),
+                Some(report.stats.mean_nanos)
+            )
+        ));
+    }
+
+    lines
+}
+
+/// Re-run a day whenever its solution, the shared library or its input changes.
+///
+/// Files are polled, so no file system notifications are needed. Every run builds the day, runs its tests and
+/// then the solution, and replaces the screen with the results.
+pub fn handle(day: Day, input: InputSource) {
+    if input == InputSource::Stdin {
+        eprintln!("`watch` cannot read the input from stdin, use `--input <path>` instead.");
+        process::exit(1);
+    }
+
+    let mut last_snapshot = None;
+    let mut previous: Vec<PartReport> = vec![];
+    let mut run_count = 0;
+
+    loop {
+        // NOTE: take the snapshot before running, so changes made during a run trigger another one.
+        let current = snapshot(watched_paths(day, &input));
+
+        if last_snapshot.as_ref() != Some(&current) {
+            last_snapshot = Some(current);
+            run_count += 1;
+
+            println!("{ANSI_ITALIC}Running day {day}...{ANSI_RESET}");
+            let outcome = run(day, &input);
+
+            print!("{CLEAR_SCREEN}");
+            for line in format_results(day, run_count, &outcome, &previous) {
+                println!("{line}");
+            }
+
+            if !outcome.output.is_empty() {
+                println!();
+                for line in &outcome.output {
+                    println!("{line}");
+                }
+            }
+
+            if !outcome.tests_passed {
+                println!();
+                let skip = outcome.test_output.len().saturating_sub(TEST_OUTPUT_LINES);
+                for line in &outcome.test_output[skip..] {
+                    println!("{line}");
+                }
+            }
+
+            println!();
+            println!("{ANSI_ITALIC}Watching for changes, press Ctrl+C to stop.{ANSI_RESET}");
+
+            if !outcome.reports.is_empty() {
+                previous = outcome.reports;
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_results, RunOutcome};
+    use crate::{
+        day,
+        template::{
+            report::{PartReport, PartStatus},
+            stats::BenchStats,
+        },
+    };
+    use std::time::Duration;
+
+    fn report(part: u8, millis: u64) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part,
+            answer: Some("42".into()),
+            status: PartStatus::Solved,
+            error: None,
+            stats: BenchStats::from_durations(&[Duration::from_millis(millis)]),
+        }
+    }
+
+    #[test]
+    fn formats_results_with_changes() {
+        let outcome = RunOutcome {
+            tests_passed: false,
+            test_output: vec![],
+            reports: vec![report(1, 10), report(2, 30)],
+            output: vec![],
+        };
+
+        let lines = format_results(day!(1), 2, &outcome, &[report(1, 20)]);
+
+        assert!(lines[0].contains("Day 01"));
+        assert!(lines[0].contains("tests failed"));
+        assert!(lines[0].contains("run #2"));
+        assert!(lines[2].contains("Part 1") && lines[2].contains("-50.0%"));
+        assert!(lines[3].contains("Part 2") && lines[3].ends_with(" -"));
+    }
+}
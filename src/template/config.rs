@@ -0,0 +1,68 @@
+/// Project configuration, read from an optional `aoc.toml` in the project root.
+///
+/// Every feature reads its own section, e.g. `[[readme]]` for the benchmark tables in the README.
+/// A missing file is the same as an empty one.
+use std::{fs, io, sync::OnceLock};
+use toml::{Table, Value};
+
+static CONFIG_FILE_PATH: &str = "./aoc.toml";
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    table: Table,
+}
+
+impl Config {
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(content) => Config::try_from(content.as_str()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// The config of the project, read once per process. Use [`Config::read_from_file`] for a fresh copy.
+    pub fn cached() -> Result<&'static Self, &'static str> {
+        static CONFIG: OnceLock<Result<Config, String>> = OnceLock::new();
+
+        CONFIG
+            .get_or_init(Self::read_from_file)
+            .as_ref()
+            .map_err(String::as_str)
+    }
+
+    /// Get a top-level section of the config, if present.
+    pub fn section(&self, name: &str) -> Option<&Value> {
+        self.table.get(name)
+    }
+}
+
+impl TryFrom<&str> for Config {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let table = value
+            .parse::<Table>()
+            .map_err(|e| format!("`aoc.toml` is not a valid TOML file: {e}"))?;
+        Ok(Config { table })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Config;
+
+    #[test]
+    fn reads_sections() {
+        let config = Config::try_from("[readme]\ntitle = \"Benchmarks\"\n").unwrap();
+        assert!(config.section("readme").is_some());
+        assert!(config.section("other").is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(Config::try_from("[readme").is_err());
+    }
+}
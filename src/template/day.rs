@@ -5,8 +5,10 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+use crate::template::Year;
+
+/// UTC offset of the website's time zone, in which puzzles unlock at midnight, in hours.
+pub(crate) const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
@@ -45,11 +47,11 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of this year's event in december, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 && today.day() <= u32::from(Year::latest().days()) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -98,22 +100,54 @@ impl Display for DayFromStrError {
     }
 }
 
+/// Parse a list of days and ranges of days, e.g. `1-5,9`. Returns the days in order, without duplicates.
+pub fn parse_days(s: &str) -> Result<Vec<Day>, String> {
+    let invalid = || format!("expecting days like `1-5,9`, got `{s}`");
+    let mut days = vec![];
+
+    for item in s.split(',') {
+        let (start, end) = item.split_once('-').unwrap_or((item, item));
+        let start: Day = start.trim().parse().map_err(|_| invalid())?;
+        let end: Day = end.trim().parse().map_err(|_| invalid())?;
+
+        if start > end {
+            return Err(invalid());
+        }
+
+        days.extend((start.0..=end.0).map(Day));
+    }
+
+    days.sort();
+    days.dedup();
+    Ok(days)
+}
+
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the current year's event, from the 1st to its last day.
 pub fn all_days() -> AllDays {
-    AllDays::new()
+    AllDays::until(Year::current().last_day())
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to a last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
+    /// Every day from the 1st to the 25th.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(Day(25))
+    }
+
+    /// Every day from the 1st to `last`.
+    pub fn until(last: Day) -> Self {
+        Self {
+            current: 1,
+            last: last.0,
+        }
     }
 }
 
@@ -121,10 +155,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -154,11 +188,11 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{parse_days, AllDays, Day};
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::new();
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +221,25 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_lists() {
+        assert_eq!(parse_days("3"), Ok(vec![Day(3)]));
+        assert_eq!(
+            parse_days("1-3,9,2"),
+            Ok(vec![Day(1), Day(2), Day(3), Day(9)])
+        );
+        assert!(parse_days("5-1").is_err());
+        assert!(parse_days("1-26").is_err());
+        assert!(parse_days("1,,2").is_err());
+    }
+
+    #[test]
+    fn short_event_iterator() {
+        let days: Vec<Day> = AllDays::until(Day(12)).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
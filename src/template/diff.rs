@@ -0,0 +1,143 @@
+/// Line-based diff of two texts, e.g. to show what `scaffold --overwrite` replaces.
+use crate::template::{ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
+
+/// Number of unchanged lines shown around every change.
+const CONTEXT: usize = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+impl DiffLine<'_> {
+    fn is_same(&self) -> bool {
+        matches!(self, DiffLine::Same(_))
+    }
+}
+
+/// Diff two texts line by line, based on their longest common subsequence of lines.
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lengths[i][j] is the length of the longest common subsequence of `old[i..]` and `new[j..]`.
+    let mut lengths = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+
+    lines.extend(old[i..].iter().map(|x| DiffLine::Removed(x)));
+    lines.extend(new[j..].iter().map(|x| DiffLine::Added(x)));
+    lines
+}
+
+/// Format the changes of a diff for the terminal, with a few unchanged lines around each of them.
+/// Returns `None` if the texts are the same.
+pub fn format_diff(lines: &[DiffLine]) -> Option<String> {
+    if lines.iter().all(DiffLine::is_same) {
+        return None;
+    }
+
+    let is_shown = |index: usize| {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + CONTEXT + 1).min(lines.len());
+        lines[start..end].iter().any(|x| !x.is_same())
+    };
+
+    let mut out = vec![];
+    let mut skipped = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        if !is_shown(index) {
+            skipped = true;
+            continue;
+        }
+
+        if skipped {
+            out.push(format!("{ANSI_ITALIC}...{ANSI_RESET}"));
+            skipped = false;
+        }
+
+        out.push(match line {
+            DiffLine::Same(x) => format!("  {x}"),
+            DiffLine::Removed(x) => format!("{ANSI_RED}- {x}{ANSI_RESET}"),
+            DiffLine::Added(x) => format!("{ANSI_GREEN}+ {x}{ANSI_RESET}"),
+        });
+    }
+
+    if skipped {
+        out.push(format!("{ANSI_ITALIC}...{ANSI_RESET}"));
+    }
+
+    Some(out.join("\n"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{diff_lines, format_diff, DiffLine};
+
+    #[test]
+    fn diffs_lines() {
+        let lines = diff_lines("a\nb\nc\nd", "a\nc\nd\ne");
+
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Same("c"),
+                DiffLine::Same("d"),
+                DiffLine::Added("e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_changes_with_context() {
+        let old = (1..=10)
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let new = old.replace("5", "five");
+
+        let formatted = format_diff(&diff_lines(&old, &new)).unwrap();
+        let lines: Vec<&str> = formatted.lines().collect();
+
+        assert_eq!(lines.len(), 8);
+        assert!(lines[0].contains("..."));
+        assert_eq!(lines[1], "  3");
+        assert!(lines[3].contains("- 5"));
+        assert!(lines[4].contains("+ five"));
+        assert_eq!(lines[6], "  7");
+        assert!(lines[7].contains("..."));
+
+        assert_eq!(format_diff(&diff_lines(&old, &old)), None);
+    }
+}
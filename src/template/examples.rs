@@ -0,0 +1,389 @@
+/// Examples of a day, checked against their expected answers by the tests that `example_tests!` generates.
+///
+/// The default example lives in `data/<year>/examples/NN.txt`. Its answers are stored in the `example` section
+/// of `data/<year>/answers/NN.json`. Any number of named examples can be added as
+/// `data/<year>/examples/NN/<name>.txt`, each with a sidecar `<name>.json` of expected answers:
+///
+/// ```json
+/// { "part_1": "10092", "part_2": null }
+/// ```
+use std::{fs, io, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    answers::{AnswerKind, Answers, PartAnswers},
+    input::InputSource,
+    registry::DaySolution,
+    report::PartReport,
+    Day, Year,
+};
+
+/// Name of the example in `data/<year>/examples/NN.txt`.
+pub const DEFAULT_EXAMPLE: &str = "example";
+
+/// Directory holding the named examples of a day.
+#[must_use]
+pub fn get_dir(day: Day) -> PathBuf {
+    Year::current()
+        .data_dir()
+        .join("examples")
+        .join(day.to_string())
+}
+
+/// Read the input and expected answers of an example.
+/// Parts without an expected answer are not checked.
+pub fn read_example(day: Day, name: &str) -> Result<(String, PartAnswers), String> {
+    if name == DEFAULT_EXAMPLE {
+        let input = InputSource::Example.read(day)?;
+        let answers = Answers::read_from_file(day)?;
+        return Ok((input, answers.get(AnswerKind::Example).clone()));
+    }
+
+    let path = get_dir(day).join(format!("{name}.txt"));
+    let input = fs::read_to_string(&path)
+        .map_err(|e| format!("could not open example file `{}`: {e}", path.display()))?;
+
+    let sidecar = path.with_extension("json");
+    let expected = match fs::read_to_string(&sidecar) {
+        Ok(content) => JsonValue::from_str(&content)
+            .map_err(|_| format!("`{}` is not a valid JSON file.", sidecar.display()))
+            .and_then(|json| PartAnswers::try_from(&json))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => PartAnswers::default(),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    Ok((input, expected))
+}
+
+/// Compare the reports of a run with the expected answers, returning a description of every mismatch.
+/// Parts that failed with an error are mismatches, even without an expected answer.
+pub fn find_mismatches(expected: &PartAnswers, reports: &[PartReport]) -> Vec<String> {
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let report = reports.iter().find(|r| r.part == part);
+
+            match (expected.get(part), report) {
+                (Some(expected), Some(r)) if r.answer.as_ref() == Some(expected) => None,
+                (Some(expected), Some(r)) => Some(format!(
+                    "Part {part}: expected `{expected}`, got `{}`",
+                    r.error.as_deref().or(r.answer.as_deref()).unwrap_or("None")
+                )),
+                (Some(expected), None) => Some(format!(
+                    "Part {part}: expected `{expected}`, but it did not run"
+                )),
+                (None, Some(r)) => r
+                    .error
+                    .as_ref()
+                    .map(|error| format!("Part {part}: failed with `{error}`")),
+                (None, None) => None,
+            }
+        })
+        .collect()
+}
+
+/// Run a solution on one of its examples and panic if any part fails or does not match its expected answer.
+///
+/// # Panics
+///
+/// Also panics if the example has no expected answer for either part, as it would check nothing.
+pub fn check(solution: &DaySolution, name: &str) {
+    let day = solution.day;
+    let (input, expected) = read_example(day, name).unwrap_or_else(|e| panic!("{e}"));
+
+    assert!(
+        !expected.is_empty(),
+        "example `{name}` of day {day} has no expected answers, add them with `cargo examples {day}`"
+    );
+
+    let mut reports = vec![];
+    (solution.run)(&input, false, &mut |report| reports.push(report));
+
+    let mismatches = find_mismatches(&expected, &reports);
+    assert!(
+        mismatches.is_empty(),
+        "example `{name}` of day {day} failed:\n{}",
+        mismatches.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An example found in a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExtractedExample {
+    pub input: String,
+    /// Expected answers, taken from the last emphasized code after the example.
+    pub answers: PartAnswers,
+}
+
+enum Block<'a> {
+    Heading(&'a str),
+    Code(String),
+    Text(String),
+}
+
+/// Split a puzzle description in the Markdown of `cargo download` into headings, code blocks and paragraphs.
+fn parse_blocks(markdown: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut lines = markdown.lines();
+    let mut text: Vec<&str> = vec![];
+
+    let flush = |text: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !text.is_empty() {
+            blocks.push(Block::Text(text.join("\n")));
+            text.clear();
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        if line.starts_with("```") {
+            flush(&mut text, &mut blocks);
+            let code: Vec<&str> = lines
+                .by_ref()
+                .take_while(|x| !x.starts_with("```"))
+                .collect();
+            blocks.push(Block::Code(code.join("\n")));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            flush(&mut text, &mut blocks);
+            blocks.push(Block::Heading(heading));
+        } else if line.trim().is_empty() {
+            flush(&mut text, &mut blocks);
+        } else {
+            text.push(line);
+        }
+    }
+
+    flush(&mut text, &mut blocks);
+    blocks
+}
+
+/// Values of all emphasized code spans, like `` *`11`* ``.
+fn emphasized_code(text: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut rest = text;
+
+    while let Some(start) = rest.find("*`") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("`*") else {
+            break;
+        };
+        values.push(rest[..end].to_string());
+        rest = &rest[end + 2..];
+    }
+
+    values
+}
+
+/// Extract the examples of a puzzle description, with the answers that the text gives for them.
+///
+/// Code blocks that follow a paragraph containing "For example" are examples. Other code blocks are
+/// illustrations and skipped. The answer of a part is the last emphasized code in the paragraphs that follow
+/// its example. Part two reuses the examples of part one, unless it introduces new ones.
+pub fn extract(markdown: &str) -> Vec<ExtractedExample> {
+    let mut examples: Vec<ExtractedExample> = vec![];
+    let mut part = 1;
+    let mut current: Option<usize> = None;
+    let mut answer: Option<String> = None;
+    let mut follows_example_intro = false;
+
+    let finish = |examples: &mut Vec<ExtractedExample>, current: Option<usize>, part, answer| {
+        if let Some(example) = current.and_then(|i| examples.get_mut(i)) {
+            if example.answers.get(part).is_none() {
+                example.answers.set(part, answer);
+            }
+        }
+    };
+
+    for block in parse_blocks(markdown) {
+        match block {
+            Block::Heading(heading) if heading.contains("Part Two") => {
+                finish(&mut examples, current, part, answer.take());
+                part = 2;
+                current = (!examples.is_empty()).then_some(0);
+            }
+            Block::Heading(_) => {}
+            Block::Code(code) if follows_example_intro => {
+                finish(&mut examples, current, part, answer.take());
+
+                let index = examples
+                    .iter()
+                    .position(|x| x.input == code)
+                    .unwrap_or_else(|| {
+                        examples.push(ExtractedExample {
+                            input: code,
+                            answers: PartAnswers::default(),
+                        });
+                        examples.len() - 1
+                    });
+
+                current = Some(index);
+                follows_example_intro = false;
+            }
+            Block::Code(_) => follows_example_intro = false,
+            Block::Text(text) => {
+                follows_example_intro = text.to_lowercase().contains("for example");
+                if current.is_some() {
+                    if let Some(value) = emphasized_code(&text).pop() {
+                        answer = Some(value);
+                    }
+                }
+            }
+        }
+    }
+
+    finish(&mut examples, current, part, answer);
+    examples
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, find_mismatches, ExtractedExample};
+    use crate::{
+        day,
+        template::{
+            answers::PartAnswers,
+            report::{PartReport, PartStatus},
+            stats::BenchStats,
+        },
+    };
+
+    fn report(part: u8, answer: Option<&str>, error: Option<&str>) -> PartReport {
+        PartReport {
+            day: day!(1),
+            part,
+            answer: answer.map(Into::into),
+            status: match (answer, error) {
+                (_, Some(_)) => PartStatus::Panicked,
+                (Some(_), _) => PartStatus::Solved,
+                _ => PartStatus::Unsolved,
+            },
+            error: error.map(Into::into),
+            stats: BenchStats::default(),
+        }
+    }
+
+    #[test]
+    fn accepts_matching_answers() {
+        let expected = PartAnswers {
+            part_1: Some("11".into()),
+            part_2: Some("31".into()),
+        };
+        let reports = [report(1, Some("11"), None), report(2, Some("31"), None)];
+        assert!(find_mismatches(&expected, &reports).is_empty());
+    }
+
+    #[test]
+    fn skips_parts_without_expected_answers() {
+        let expected = PartAnswers {
+            part_1: Some("11".into()),
+            part_2: None,
+        };
+        let reports = [report(1, Some("11"), None), report(2, None, None)];
+        assert!(find_mismatches(&expected, &reports).is_empty());
+    }
+
+    #[test]
+    fn reports_mismatches() {
+        let expected = PartAnswers {
+            part_1: Some("11".into()),
+            part_2: Some("31".into()),
+        };
+        let reports = [report(1, Some("12"), None), report(2, None, Some("oops"))];
+        assert_eq!(
+            find_mismatches(&expected, &reports),
+            vec![
+                "Part 1: expected `11`, got `12`".to_string(),
+                "Part 2: expected `31`, got `oops`".to_string()
+            ]
+        );
+        assert_eq!(find_mismatches(&expected, &reports[..1]).len(), 2);
+    }
+
+    #[test]
+    fn reports_errors_without_expected_answers() {
+        let expected = PartAnswers {
+            part_1: Some("11".into()),
+            part_2: None,
+        };
+        let reports = [report(1, Some("11"), None), report(2, None, Some("oops"))];
+        assert_eq!(
+            find_mismatches(&expected, &reports),
+            vec!["Part 2: failed with `oops`".to_string()]
+        );
+    }
+
+    #[test]
+    fn extracts_examples_from_puzzles() {
+        let markdown = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "Pair up the numbers:",
+            "",
+            "```",
+            "3 - 4",
+            "```",
+            "",
+            "The first pair has a distance of *`1`*, the total is *`11`*.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Here is the *`same`* example list again:",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "So, for these example lists, the score is *`31`*.",
+            "",
+            "For example, consider a larger example:",
+            "",
+            "```",
+            "1   1",
+            "```",
+            "",
+            "Its score would be *`2`*.",
+        ]
+        .join("\n");
+
+        let examples = extract(&markdown);
+        assert_eq!(
+            examples,
+            vec![
+                ExtractedExample {
+                    input: "3   4\n4   3".into(),
+                    answers: PartAnswers {
+                        part_1: Some("11".into()),
+                        part_2: Some("31".into()),
+                    },
+                },
+                ExtractedExample {
+                    input: "1   1".into(),
+                    answers: PartAnswers {
+                        part_1: None,
+                        part_2: Some("2".into()),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reuses_examples_in_part_two() {
+        let markdown = "For example:\n\n```\nabc\n```\n\nThat is *`3`*.\n\n## --- Part Two ---\n\nNow it is *`6`*.";
+        let examples = extract(markdown);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].answers.part_1, Some("3".into()));
+        assert_eq!(examples[0].answers.part_2, Some("6".into()));
+    }
+}
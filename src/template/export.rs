@@ -0,0 +1,396 @@
+/// Exports benchmark timings to files that can be shared outside of the README, selected with
+/// `time --export <format> --out <file>`.
+use std::{fmt::Write, fs, io, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    report::PartReport,
+    runner::part_label,
+    stats::BenchStats,
+    timings::{format_nanos, PartTiming, Timings},
+    Day,
+};
+
+/// File format of an export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One row per part with raw nanos.
+    Csv,
+    /// A Markdown table with formatted durations.
+    Markdown,
+    /// A self-contained HTML report with a sortable table and a bar chart of the medians.
+    Html,
+    /// One timing per line, in the same schema as `data/timings.json`.
+    JsonLines,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "md" => Ok(ExportFormat::Markdown),
+            "html" => Ok(ExportFormat::Html),
+            "jsonl" => Ok(ExportFormat::JsonLines),
+            x => Err(format!(
+                "unknown export format `{x}`, expecting `csv`, `md`, `html` or `jsonl`."
+            )),
+        }
+    }
+}
+
+/// Timing of a single part of a day.
+struct Row<'a> {
+    day: Day,
+    part: u8,
+    timing: &'a PartTiming,
+}
+
+impl Row<'_> {
+    fn median_nanos(&self) -> Option<f64> {
+        self.timing.is_solved().then(|| self.timing.median_nanos())
+    }
+
+    fn stat(&self, value: impl Fn(&BenchStats) -> f64) -> Option<f64> {
+        self.timing.stats.as_ref().map(value)
+    }
+}
+
+fn rows(timings: &Timings) -> Vec<Row<'_>> {
+    timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            [PartReport::PARSE, 1, 2]
+                .into_iter()
+                .filter_map(move |part| {
+                    Some(Row {
+                        day: timing.day,
+                        part,
+                        timing: timing.part(part)?,
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Render timings in an export format.
+pub fn render(format: ExportFormat, timings: &Timings) -> String {
+    match format {
+        ExportFormat::Csv => render_csv(timings),
+        ExportFormat::Markdown => render_markdown(timings),
+        ExportFormat::Html => render_html(timings),
+        ExportFormat::JsonLines => render_json_lines(timings),
+    }
+}
+
+/// Render timings in an export format and write them to a file.
+pub fn write(format: ExportFormat, timings: &Timings, path: &Path) -> Result<(), io::Error> {
+    fs::write(path, render(format, timings))
+}
+
+fn format_optional(nanos: Option<f64>) -> String {
+    nanos.map_or_else(|| "-".into(), format_nanos)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_csv(timings: &Timings) -> String {
+    let mut out = String::from(
+        "day,part,status,mean_nanos,median_nanos,min_nanos,max_nanos,stddev_nanos,samples\n",
+    );
+
+    let cell = |nanos: Option<f64>| nanos.map(|x| x.to_string()).unwrap_or_default();
+
+    for row in rows(timings) {
+        let part = if row.part == PartReport::PARSE {
+            "parse".to_string()
+        } else {
+            row.part.to_string()
+        };
+
+        writeln!(
+            out,
+            "{},{part},{},{},{},{},{},{},{}",
+            row.day,
+            row.timing.status.as_str(),
+            row.timing.nanos,
+            cell(row.median_nanos()),
+            cell(row.stat(|s| s.min_nanos)),
+            cell(row.stat(|s| s.max_nanos)),
+            cell(row.stat(|s| s.stddev_nanos)),
+            row.timing.samples
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+fn render_markdown(timings: &Timings) -> String {
+    let mut lines = vec![
+        "| Day | Part | Mean | Median | Min | Max | Samples |".to_string(),
+        "| :---: | :---: | ---: | ---: | ---: | ---: | ---: |".to_string(),
+    ];
+
+    for row in rows(timings) {
+        lines.push(format!(
+            "| {} | {} | `{}` | `{}` | `{}` | `{}` | {} |",
+            row.day.into_inner(),
+            part_label(row.part),
+            row.timing.format(),
+            format_optional(row.median_nanos()),
+            format_optional(row.stat(|s| s.min_nanos)),
+            format_optional(row.stat(|s| s.max_nanos)),
+            row.timing.samples
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+    lines.push(String::new());
+
+    lines.join("\n")
+}
+
+fn render_json_lines(timings: &Timings) -> String {
+    timings
+        .data
+        .iter()
+        .map(|timing| JsonValue::from(timing).stringify().unwrap() + "\n")
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+const BAR_WIDTH: f64 = 240.0;
+const BAR_HEIGHT: f64 = 14.0;
+
+static HTML_STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3rem 0.8rem; border-bottom: 1px solid #d0d7de; text-align: right; }
+th { cursor: pointer; user-select: none; background: #f6f8fa; }
+th[aria-sort=ascending]::after { content: ' ▲'; }
+th[aria-sort=descending]::after { content: ' ▼'; }
+td.timeout { color: #cf222e; }
+rect.parse { fill: #8c959f; }
+rect.part { fill: #0969da; }
+";
+
+// NOTE: cells carry their raw value in `data-sort`, so durations sort numerically.
+static HTML_SCRIPT: &str = "
+document.querySelectorAll('th').forEach((th, column) => th.addEventListener('click', () => {
+  const body = th.closest('table').tBodies[0];
+  const ascending = th.getAttribute('aria-sort') !== 'ascending';
+  th.parentNode.querySelectorAll('th').forEach((x) => x.removeAttribute('aria-sort'));
+  th.setAttribute('aria-sort', ascending ? 'ascending' : 'descending');
+  const value = (row) => {
+    const cell = row.cells[column];
+    const raw = cell.dataset.sort ?? cell.textContent;
+    return raw === '' || isNaN(raw) ? raw : Number(raw);
+  };
+  [...body.rows]
+    .sort((a, b) => (value(a) > value(b) ? 1 : value(a) < value(b) ? -1 : 0) * (ascending ? 1 : -1))
+    .forEach((row) => body.appendChild(row));
+}));
+";
+
+fn bar(row: &Row, max_nanos: f64) -> String {
+    let Some(nanos) = row.median_nanos() else {
+        return String::new();
+    };
+
+    let width = if max_nanos > 0.0 {
+        (nanos / max_nanos * BAR_WIDTH).max(1.0)
+    } else {
+        0.0
+    };
+
+    let class = if row.part == PartReport::PARSE {
+        "parse"
+    } else {
+        "part"
+    };
+
+    format!(
+        "<svg width=\"{BAR_WIDTH}\" height=\"{BAR_HEIGHT}\"><rect class=\"{class}\" width=\"{width:.1}\" height=\"{BAR_HEIGHT}\"><title>{}</title></rect></svg>",
+        format_nanos(nanos)
+    )
+}
+
+fn render_html(timings: &Timings) -> String {
+    let rows = rows(timings);
+    let max_nanos = rows
+        .iter()
+        .filter_map(Row::median_nanos)
+        .fold(0.0, f64::max);
+
+    let mut out = String::new();
+
+    writeln!(out, "<!DOCTYPE html>").unwrap();
+    writeln!(out, "<html lang=\"en\">").unwrap();
+    writeln!(out, "<head>").unwrap();
+    writeln!(out, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(out, "<title>Advent of Code benchmarks</title>").unwrap();
+    writeln!(out, "<style>{HTML_STYLE}</style>").unwrap();
+    writeln!(out, "</head>").unwrap();
+    writeln!(out, "<body>").unwrap();
+    writeln!(out, "<h1>Benchmarks</h1>").unwrap();
+    writeln!(out, "<table>").unwrap();
+    writeln!(
+        out,
+        "<thead><tr><th>Day</th><th>Part</th><th>Mean</th><th>Median</th><th>Min</th><th>Max</th><th>Samples</th><th>Chart</th></tr></thead>"
+    )
+    .unwrap();
+    writeln!(out, "<tbody>").unwrap();
+
+    let cell = |nanos: Option<f64>| {
+        format!(
+            "<td data-sort=\"{}\">{}</td>",
+            nanos.map(|x| x.to_string()).unwrap_or_default(),
+            format_optional(nanos)
+        )
+    };
+
+    for row in &rows {
+        let mean = if row.timing.is_solved() {
+            cell(Some(row.timing.nanos))
+        } else {
+            "<td class=\"timeout\" data-sort=\"\">TIMEOUT</td>".into()
+        };
+
+        writeln!(
+            out,
+            "<tr><td data-sort=\"{}\">{}</td><td data-sort=\"{}\">{}</td>{mean}{}{}{}<td>{}</td><td data-sort=\"{}\">{}</td></tr>",
+            row.day.into_inner(),
+            row.day.into_inner(),
+            row.part,
+            part_label(row.part),
+            cell(row.median_nanos()),
+            cell(row.stat(|s| s.min_nanos)),
+            cell(row.stat(|s| s.max_nanos)),
+            row.timing.samples,
+            row.median_nanos().map(|x| x.to_string()).unwrap_or_default(),
+            bar(row, max_nanos)
+        )
+        .unwrap();
+    }
+
+    writeln!(out, "</tbody>").unwrap();
+    writeln!(out, "</table>").unwrap();
+    writeln!(
+        out,
+        "<p><strong>Total: {:.2}ms</strong></p>",
+        timings.total_millis()
+    )
+    .unwrap();
+    writeln!(out, "<script>{HTML_SCRIPT}</script>").unwrap();
+    writeln!(out, "</body>").unwrap();
+    writeln!(out, "</html>").unwrap();
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, ExportFormat};
+    use crate::{
+        day,
+        template::{
+            report::PartStatus,
+            stats::BenchStats,
+            timings::{PartTiming, Timing, Timings},
+        },
+    };
+    use std::str::FromStr;
+
+    fn part(status: PartStatus, median_nanos: f64) -> PartTiming {
+        PartTiming {
+            status,
+            nanos: median_nanos,
+            samples: 10,
+            stats: (status == PartStatus::Solved).then(|| BenchStats {
+                samples: 10,
+                median_nanos,
+                min_nanos: median_nanos,
+                max_nanos: median_nanos,
+                ..BenchStats::default()
+            }),
+        }
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    parse: Some(part(PartStatus::Solved, 500.0)),
+                    part_1: Some(part(PartStatus::Solved, 1_000.0)),
+                    part_2: Some(part(PartStatus::Solved, 2_000_000.0)),
+                    total_nanos: 2_001_500.0,
+                },
+                Timing {
+                    day: day!(2),
+                    parse: None,
+                    part_1: Some(part(PartStatus::Solved, 3_000.0)),
+                    part_2: Some(part(PartStatus::Timeout, 0.0)),
+                    total_nanos: 3_000.0,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(ExportFormat::from_str("md"), Ok(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::from_str("jsonl"), Ok(ExportFormat::JsonLines));
+        assert!(ExportFormat::from_str("xlsx").is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = render(ExportFormat::Csv, &get_mock_timings());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[1], "01,parse,solved,500,500,500,500,0,10");
+        assert_eq!(lines[5], "02,2,timeout,0,,,,,10");
+    }
+
+    #[test]
+    fn exports_markdown() {
+        let md = render(ExportFormat::Markdown, &get_mock_timings());
+        assert!(md.contains("| 1 | Part 2 | `2.0ms` | `2.0ms` | `2.0ms` | `2.0ms` | 10 |"));
+        assert!(md.contains("| 2 | Part 2 | `TIMEOUT` | `-` | `-` | `-` | 10 |"));
+        assert!(md.contains("**Total: 2.00ms**"));
+    }
+
+    #[test]
+    fn exports_json_lines() {
+        let jsonl = render(ExportFormat::JsonLines, &get_mock_timings());
+        let read = Timings::try_from(format!(
+            "{{ \"data\": [{}] }}",
+            jsonl.lines().collect::<Vec<_>>().join(",")
+        ))
+        .unwrap();
+        assert_eq!(read.data.len(), 2);
+        assert_eq!(
+            read.data[1].part_2.as_ref().unwrap().status,
+            PartStatus::Timeout
+        );
+    }
+
+    #[test]
+    fn exports_self_contained_html() {
+        let html = render(ExportFormat::Html, &get_mock_timings());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<svg").count(), 4);
+        assert!(html.contains("width=\"240.0\" height=\"14\""));
+        assert!(html.contains("<script>"));
+        assert!(!html.contains("src=\""));
+        assert!(!html.contains("href=\""));
+    }
+}
@@ -0,0 +1,480 @@
+/// Append-only history of benchmark runs, stored as one JSON object per line in `data/<year>/timings_history.jsonl`.
+///
+/// Unlike `data/<year>/timings.json`, which only keeps the latest timing of each day, every run of `cargo time`
+/// is kept together with the time it ran at, the checked out commit and the build profile.
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    report::PartReport,
+    timings::{PartTiming, Timing, Timings},
+    Day, Year,
+};
+
+fn history_file_path() -> PathBuf {
+    Year::current().data_dir().join("timings_history.jsonl")
+}
+
+/// Default threshold, in percent, above which a slower median counts as a regression.
+pub const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the commit that was checked out, if the project is a git repository.
+    pub commit: Option<String>,
+    /// Cargo profile the solutions were built with.
+    pub profile: String,
+    pub timings: Timings,
+    /// Whether `cargo time --compare` flagged the run as a regression. Such runs are never used as a baseline,
+    /// so re-running the comparison cannot pass against them.
+    pub regressed: bool,
+}
+
+impl HistoryEntry {
+    /// Record a run that finished just now.
+    pub fn new(timings: Timings, profile: &str) -> Self {
+        HistoryEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+            commit: current_commit(),
+            profile: profile.into(),
+            timings,
+            regressed: false,
+        }
+    }
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let hash = String::from_utf8(output.stdout).ok()?;
+    Some(hash.trim().to_string()).filter(|x| !x.is_empty())
+}
+
+/// All recorded benchmark runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Read the history. If no runs were recorded yet, returns an empty history.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(history_file_path()) {
+            Ok(content) => History::try_from(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Append a run to the history file.
+    pub fn append_file(entry: &HistoryEntry) -> Result<(), io::Error> {
+        let line = JsonValue::from(entry)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let path = history_file_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        writeln!(file, "{line}")
+    }
+
+    /// Most recent timing of a day that was recorded with a profile, skipping regressed runs.
+    pub fn latest(&self, day: Day, profile: &str) -> Option<(&HistoryEntry, &Timing)> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.profile == profile && !entry.regressed)
+            .find_map(|entry| {
+                let timing = entry.timings.data.iter().find(|t| t.day == day)?;
+                Some((entry, timing))
+            })
+    }
+}
+
+/// Median of a day in the current run compared to its baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+    /// Commit of the baseline run, if known.
+    pub baseline_commit: Option<String>,
+    /// Parts that were solved in the baseline, but timed out, panicked or returned no answer in this run.
+    pub broken_parts: Vec<u8>,
+}
+
+impl Comparison {
+    /// Relative change of the median in percent. Positive values mean the day got slower.
+    pub fn change_percent(&self) -> f64 {
+        (self.current_nanos - self.baseline_nanos) / self.baseline_nanos * 100.0
+    }
+
+    pub fn is_broken(&self) -> bool {
+        !self.broken_parts.is_empty()
+    }
+
+    /// Whether the day got slower than the threshold allows. Broken days always count as regressions.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.is_broken() || self.change_percent() > threshold
+    }
+}
+
+/// Compare the medians of a run with the latest recorded run of each benched day with the same profile.
+/// Days without a baseline are skipped. A benched day without a timing in `current`, e.g. because it failed
+/// to build or crashed before its first part, counts as broken.
+pub fn compare(
+    history: &History,
+    days: &HashSet<Day>,
+    current: &Timings,
+    profile: &str,
+) -> Vec<Comparison> {
+    let mut days: Vec<Day> = days.iter().copied().collect();
+    days.sort_unstable();
+
+    days.into_iter()
+        .filter_map(|day| {
+            let (entry, baseline) = history.latest(day, profile)?;
+
+            let baseline_nanos = baseline.median_nanos();
+            if baseline_nanos <= 0.0 {
+                return None;
+            }
+
+            let timing = current.data.iter().find(|t| t.day == day);
+
+            let is_solved =
+                |timing: &Timing, part: u8| timing.part(part).is_some_and(PartTiming::is_solved);
+            let broken_parts = [PartReport::PARSE, 1, 2]
+                .into_iter()
+                .filter(|part| {
+                    is_solved(baseline, *part) && !timing.is_some_and(|t| is_solved(t, *part))
+                })
+                .collect();
+
+            Some(Comparison {
+                day,
+                baseline_nanos,
+                current_nanos: timing.map_or(0.0, Timing::median_nanos),
+                baseline_commit: entry.commit.clone(),
+                broken_parts,
+            })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("regressed".into(), JsonValue::Boolean(value.regressed));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.profile to be a string.")?;
+
+        // NOTE: runs recorded before regressions were marked have no `regressed` key.
+        let regressed = json
+            .get("regressed")
+            .map_or(Some(&false), |v| v.get::<bool>())
+            .ok_or("Expected entry.regressed to be a boolean.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.data to be an array.")?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit: commit.cloned(),
+            profile: profile.clone(),
+            regressed: *regressed,
+            timings: Timings {
+                data: data
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let entries = value
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                JsonValue::from_str(line)
+                    .map_err(|_| "not valid JSON.".to_string())
+                    .and_then(|json| HistoryEntry::try_from(&json))
+                    .map_err(|e| format!("line {}: {e}", i + 1))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { entries })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, History, HistoryEntry};
+    use crate::{
+        day,
+        template::{
+            report::PartStatus,
+            timings::{PartTiming, Timing, Timings},
+            Day,
+        },
+    };
+    use std::collections::HashSet;
+    use tinyjson::JsonValue;
+
+    fn part(nanos: f64) -> PartTiming {
+        PartTiming {
+            status: PartStatus::Solved,
+            nanos,
+            samples: 1,
+            stats: None,
+        }
+    }
+
+    fn timing(day: Day, total_nanos: f64) -> Timing {
+        Timing {
+            day,
+            parse: None,
+            part_1: Some(part(total_nanos / 2.0)),
+            part_2: Some(part(total_nanos / 2.0)),
+            total_nanos,
+        }
+    }
+
+    fn entry(commit: &str, profile: &str, data: Vec<Timing>) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: 1_733_000_000,
+            commit: Some(commit.into()),
+            profile: profile.into(),
+            timings: Timings { data },
+            regressed: false,
+        }
+    }
+
+    fn days(timings: &Timings) -> HashSet<Day> {
+        timings.data.iter().map(|t| t.day).collect()
+    }
+
+    fn to_line(entry: &HistoryEntry) -> String {
+        JsonValue::from(entry).stringify().unwrap()
+    }
+
+    #[test]
+    fn reads_history_lines() {
+        let content = [
+            to_line(&entry("abc1234", "release", vec![timing(day!(1), 100.0)])),
+            String::new(),
+            to_line(&entry("def5678", "release", vec![timing(day!(2), 200.0)])),
+        ]
+        .join("\n");
+
+        let history = History::try_from(content).unwrap();
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[0].timestamp, 1_733_000_000);
+        assert_eq!(history.entries[1].commit, Some("def5678".into()));
+        assert_eq!(history.entries[1].timings.data[0].day, day!(2));
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let err = History::try_from("{}\n".to_string()).unwrap_err();
+        assert!(err.starts_with("line 1:"));
+    }
+
+    #[test]
+    fn compares_with_latest_runs() {
+        let history = History {
+            entries: vec![
+                entry(
+                    "old",
+                    "release",
+                    vec![timing(day!(1), 50.0), timing(day!(2), 100.0)],
+                ),
+                entry("new", "release", vec![timing(day!(1), 100.0)]),
+                entry("debug", "debug", vec![timing(day!(1), 1000.0)]),
+            ],
+        };
+
+        let current = Timings {
+            data: vec![
+                timing(day!(1), 120.0),
+                timing(day!(2), 95.0),
+                timing(day!(3), 10.0),
+            ],
+        };
+
+        let comparisons = compare(&history, &days(&current), &current, "release");
+        assert_eq!(comparisons.len(), 2);
+
+        assert_eq!(comparisons[0].baseline_commit, Some("new".into()));
+        assert_eq!(comparisons[0].baseline_nanos, 100.0);
+        assert!((comparisons[0].change_percent() - 20.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(25.0));
+
+        assert_eq!(comparisons[1].baseline_commit, Some("old".into()));
+        assert!(!comparisons[1].is_regression(0.0));
+    }
+
+    #[test]
+    fn flags_broken_days() {
+        let history = History {
+            entries: vec![entry("old", "release", vec![timing(day!(1), 100.0)])],
+        };
+
+        let mut broken = timing(day!(1), 50.0);
+        broken.part_2 = Some(PartTiming {
+            status: PartStatus::Timeout,
+            ..part(5e9)
+        });
+
+        let current = Timings { data: vec![broken] };
+        let comparisons = compare(&history, &days(&current), &current, "release");
+
+        assert_eq!(comparisons[0].broken_parts, vec![2]);
+        assert!(comparisons[0].is_regression(1000.0));
+
+        let mut unsolved = timing(day!(1), 0.0);
+        unsolved.part_1 = None;
+        unsolved.part_2 = None;
+
+        let current = Timings {
+            data: vec![unsolved],
+        };
+        let comparisons = compare(&history, &days(&current), &current, "release");
+        assert_eq!(comparisons[0].broken_parts, vec![1, 2]);
+    }
+
+    #[test]
+    fn flags_days_without_timings() {
+        let history = History {
+            entries: vec![entry(
+                "old",
+                "release",
+                vec![timing(day!(1), 100.0), timing(day!(2), 100.0)],
+            )],
+        };
+
+        // NOTE: day 2 was benched, but crashed before reporting any part.
+        let current = Timings {
+            data: vec![timing(day!(1), 100.0)],
+        };
+        let benched = HashSet::from([day!(1), day!(2), day!(3)]);
+        let comparisons = compare(&history, &benched, &current, "release");
+
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].is_broken());
+        assert_eq!(comparisons[1].day, day!(2));
+        assert_eq!(comparisons[1].broken_parts, vec![1, 2]);
+        assert!(comparisons[1].is_regression(1000.0));
+    }
+
+    #[test]
+    fn skips_regressed_runs_as_baseline() {
+        let mut regressed = entry("slow", "release", vec![timing(day!(1), 200.0)]);
+        regressed.regressed = true;
+
+        let history = History {
+            entries: vec![
+                entry("fast", "release", vec![timing(day!(1), 100.0)]),
+                regressed,
+            ],
+        };
+
+        let (entry, _) = history.latest(day!(1), "release").unwrap();
+        assert_eq!(entry.commit, Some("fast".into()));
+
+        // NOTE: running the slow code again still regresses against the last good run.
+        let current = Timings {
+            data: vec![timing(day!(1), 200.0)],
+        };
+        let comparisons = compare(&history, &days(&current), &current, "release");
+        assert!(comparisons[0].is_regression(10.0));
+
+        let line = to_line(&history.entries[1]);
+        let history = History::try_from(line).unwrap();
+        assert!(history.entries[0].regressed);
+        assert!(history.latest(day!(1), "release").is_none());
+    }
+}
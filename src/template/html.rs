@@ -0,0 +1,321 @@
+//! Converts the puzzle pages of Advent of Code to Markdown.
+//!
+//! This only covers the small subset of HTML that the puzzle descriptions use: headings, paragraphs, lists,
+//! links, emphasis and code.
+
+/// Text of all `<article>` elements of a page, converted to Markdown.
+/// Relative links are resolved against `base_url`.
+pub fn articles_to_markdown(html: &str, base_url: &str) -> String {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let content_end = rest[content_start..]
+            .find("</article>")
+            .map_or(rest.len(), |x| content_start + x);
+
+        articles.push(to_markdown(&rest[content_start..content_end], base_url));
+        rest = &rest[content_end..];
+    }
+
+    articles.join("\n\n")
+}
+
+/// A parsed HTML tag.
+struct Tag<'a> {
+    name: String,
+    is_closing: bool,
+    attributes: &'a str,
+}
+
+impl Tag<'_> {
+    fn parse(s: &str) -> Tag<'_> {
+        let (is_closing, s) = match s.strip_prefix('/') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let s = s.trim_end_matches('/');
+        let (name, attributes) = s.split_once(char::is_whitespace).unwrap_or((s, ""));
+
+        Tag {
+            name: name.to_ascii_lowercase(),
+            is_closing,
+            attributes,
+        }
+    }
+
+    fn attribute(&self, name: &str) -> Option<String> {
+        let start = self.attributes.find(&format!("{name}=\""))? + name.len() + 2;
+        let end = self.attributes[start..].find('"')? + start;
+        Some(decode_entities(&self.attributes[start..end]))
+    }
+}
+
+/// Convert a fragment of HTML to Markdown.
+pub fn to_markdown(html: &str, base_url: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    let mut in_pre = false;
+    let mut code_depth = 0;
+    let mut is_emphasized_code = false;
+    let mut list_depth: usize = 0;
+    let mut links: Vec<Option<String>> = vec![];
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_end) = rest[tag_start..].find('>').map(|x| tag_start + x) else {
+            push_text(&mut out, &rest[tag_start..], in_pre);
+            break;
+        };
+
+        let tag = Tag::parse(&rest[tag_start + 1..tag_end]);
+        rest = &rest[tag_end + 1..];
+
+        match (tag.name.as_str(), tag.is_closing) {
+            ("h1" | "h2" | "h3", false) => {
+                start_block(&mut out);
+                out.push_str("## ");
+            }
+            ("p", false) => start_block(&mut out),
+            ("h1" | "h2" | "h3" | "p", true) => start_block(&mut out),
+            ("pre", false) => {
+                start_block(&mut out);
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```");
+                start_block(&mut out);
+                in_pre = false;
+            }
+            // NOTE: answers are emphasized as `<code><em>11</em></code>`, which Markdown can only express
+            // the other way around.
+            ("code", false) if !in_pre && code_depth == 0 && rest.starts_with("<em>") => {
+                rest = &rest["<em>".len()..];
+                code_depth += 1;
+                is_emphasized_code = true;
+                out.push_str("*`");
+            }
+            ("code", true) if is_emphasized_code => {
+                code_depth -= 1;
+                is_emphasized_code = false;
+                out.push_str("`*");
+            }
+            ("code", is_closing) if !in_pre => {
+                if is_closing {
+                    code_depth -= 1;
+                } else {
+                    code_depth += 1;
+                }
+                out.push('`');
+            }
+            ("em" | "strong", _) if !in_pre && code_depth == 0 => out.push('*'),
+            ("ul" | "ol", false) => {
+                if list_depth == 0 {
+                    start_block(&mut out);
+                }
+                list_depth += 1;
+            }
+            ("ul" | "ol", true) => {
+                list_depth = list_depth.saturating_sub(1);
+                if list_depth == 0 {
+                    start_block(&mut out);
+                }
+            }
+            ("li", false) => {
+                trim_trailing_spaces(&mut out);
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str(&"  ".repeat(list_depth.saturating_sub(1)));
+                out.push_str("- ");
+            }
+            ("a", false) => {
+                let href = tag.attribute("href").map(|href| {
+                    if href.starts_with('/') {
+                        format!("{}{href}", base_url.trim_end_matches('/'))
+                    } else {
+                        href
+                    }
+                });
+                if href.is_some() {
+                    out.push('[');
+                }
+                links.push(href);
+            }
+            ("a", true) => {
+                if let Some(Some(href)) = links.pop() {
+                    out.push_str(&format!("]({href})"));
+                }
+            }
+            ("br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    let mut markdown = String::new();
+    for line in out.lines() {
+        if line.trim().is_empty() && markdown.ends_with("\n\n") {
+            continue;
+        }
+        markdown.push_str(line.trim_end());
+        markdown.push('\n');
+    }
+
+    markdown.trim().to_string()
+}
+
+fn trim_trailing_spaces(out: &mut String) {
+    while out.ends_with(' ') {
+        out.pop();
+    }
+}
+
+/// Make sure the next text starts a new block.
+fn start_block(out: &mut String) {
+    trim_trailing_spaces(out);
+    if out.is_empty() {
+        return;
+    }
+    while !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+
+    // NOTE: outside of `<pre>`, whitespace in HTML is not significant.
+    for (i, word) in text.split(char::is_whitespace).enumerate() {
+        if i > 0 && !out.is_empty() && !out.ends_with([' ', '\n']) {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|x| u32::from_str_radix(x, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|x| x.parse().ok()))
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles_to_markdown, to_markdown};
+
+    static BASE_URL: &str = "https://adventofcode.com";
+
+    #[test]
+    fn converts_puzzle_articles() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is always present.</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>Pair up the <code>&lt;smallest&gt;</code> numbers, see <a href="/2024/about">about</a>.</p>
+<ul><li>Item <code><em>1</em></code></li><li>Item 2<ul><li>Nested</li></ul></li></ul>
+</article>
+<p>Your puzzle answer was <code>11</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now count&nbsp;them &amp; <a href="https://example.com" target="_blank">more</a>.</p></article>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "The *Chief Historian* is always present.",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "Pair up the `<smallest>` numbers, see [about](https://adventofcode.com/2024/about).",
+            "",
+            "- Item *`1`*",
+            "- Item 2",
+            "  - Nested",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Now count them & [more](https://example.com).",
+        ]
+        .join("\n");
+
+        assert_eq!(articles_to_markdown(html, BASE_URL), expected);
+    }
+
+    #[test]
+    fn converts_answer_responses() {
+        let html = "<main>\n<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer. <a href=\"/2024\">[Return to Advent Calendar]</a></p></article>\n</main>";
+        assert_eq!(
+            articles_to_markdown(html, BASE_URL),
+            "That's the right answer! You are one gold star closer. [[Return to Advent Calendar]](https://adventofcode.com/2024)"
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_entities() {
+        assert_eq!(
+            to_markdown("a &unknown; b &#65; &#x42;", BASE_URL),
+            "a &unknown; b A B"
+        );
+    }
+}
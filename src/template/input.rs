@@ -0,0 +1,70 @@
+/// Selects the puzzle input of a solution binary.
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+use crate::template::{solution_args::SolutionArgs, Day, Year};
+
+/// Where a solution binary reads its puzzle input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// `data/<year>/inputs/NN.txt`.
+    #[default]
+    Input,
+    /// `data/<year>/examples/NN.txt`, selected with `--example`.
+    Example,
+    /// An arbitrary file, selected with `--input <path>`.
+    Path(PathBuf),
+    /// Standard input, selected with `--input -`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Select an input source from the values of the `--example` and `--input <path>` flags.
+    pub fn from_flags(is_example: bool, path: Option<&str>) -> Result<Self, String> {
+        match (is_example, path) {
+            (true, Some(_)) => Err("`--input` and `--example` cannot be used together.".into()),
+            (true, None) => Ok(InputSource::Example),
+            (false, Some("-")) => Ok(InputSource::Stdin),
+            (false, Some(path)) => Ok(InputSource::Path(path.into())),
+            (false, None) => Ok(InputSource::Input),
+        }
+    }
+
+    /// Read the input of a day from this source.
+    pub fn read(&self, day: Day) -> Result<String, String> {
+        let path = match self {
+            InputSource::Input => data_path("inputs", day),
+            InputSource::Example => data_path("examples", day),
+            InputSource::Path(path) => path.clone(),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("could not read input from stdin: {e}"))?;
+                return Ok(input);
+            }
+        };
+
+        fs::read_to_string(&path)
+            .map_err(|e| format!("could not open input file `{}`: {e}", path.display()))
+    }
+}
+
+fn data_path(folder: &str, day: Day) -> PathBuf {
+    let cwd = env::current_dir().unwrap();
+    cwd.join(Year::current().data_dir())
+        .join(folder)
+        .join(format!("{day}.txt"))
+}
+
+/// Read the input of a day from the source selected on the command line. Exits the process if it cannot be read.
+pub fn read_input(day: Day) -> String {
+    SolutionArgs::current().input.read(day).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    })
+}
@@ -0,0 +1,242 @@
+/// Integrity manifest of the downloaded puzzle inputs, stored in `data/<year>/inputs/manifest.json`.
+///
+/// Every download records the SHA-256 of the input and when it was fetched, so inputs that were edited
+/// afterwards can be told apart from the real ones.
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::get_input_path, Day, Year};
+
+fn manifest_file_path() -> PathBuf {
+    Year::current()
+        .data_dir()
+        .join("inputs")
+        .join("manifest.json")
+}
+
+/// Hex-encoded SHA-256 of some content.
+pub fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|x| format!("{x:02x}"))
+        .collect()
+}
+
+/// Manifest entry of a single input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub day: Day,
+    pub sha256: String,
+    /// Seconds since the Unix epoch.
+    pub fetched_at: u64,
+}
+
+/// Whether an input is still the one that was downloaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Integrity {
+    /// The input was not downloaded, e.g. because it was pasted by hand.
+    Unknown,
+    Intact,
+    Modified,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub data: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Read the manifest. If no input was downloaded yet, returns an empty manifest.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(manifest_file_path()) {
+            Ok(content) => Manifest::try_from(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(manifest_file_path())?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, day: Day) -> Option<&ManifestEntry> {
+        self.data.iter().find(|x| x.day == day)
+    }
+
+    /// Record an input that was fetched just now, replacing an earlier entry of the day.
+    pub fn record(&mut self, day: Day, content: &str) {
+        let entry = ManifestEntry {
+            day,
+            sha256: sha256_hex(content.as_bytes()),
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+        };
+
+        self.data.retain(|x| x.day != day);
+        self.data.push(entry);
+        self.data.sort_by_key(|x| x.day);
+    }
+
+    /// Check the content of an input against its recorded hash.
+    pub fn check(&self, day: Day, content: &str) -> Integrity {
+        match self.get(day) {
+            None => Integrity::Unknown,
+            Some(entry) if entry.sha256 == sha256_hex(content.as_bytes()) => Integrity::Intact,
+            Some(_) => Integrity::Modified,
+        }
+    }
+
+    /// Check the input of a day on disk against its recorded hash.
+    pub fn check_file(&self, day: Day) -> Integrity {
+        match fs::read_to_string(get_input_path(day)) {
+            Ok(content) => self.check(day, &content),
+            Err(_) => Integrity::Unknown,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&ManifestEntry> for JsonValue {
+    fn from(value: &ManifestEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("sha256".into(), JsonValue::String(value.sha256.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "fetched_at".into(),
+            JsonValue::Number(value.fetched_at as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for ManifestEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected manifest entry to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        let sha256 = json
+            .get("sha256")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.sha256 to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let fetched_at = json
+            .get("fetched_at")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected entry.fetched_at to be a number.")?;
+
+        Ok(ManifestEntry {
+            day,
+            sha256: sha256.clone(),
+            fetched_at,
+        })
+    }
+}
+
+impl From<Manifest> for JsonValue {
+    fn from(value: Manifest) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Manifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected manifest.data to be an array.")?;
+
+        Ok(Manifest {
+            data: data
+                .iter()
+                .map(ManifestEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{sha256_hex, Integrity, Manifest};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn hashes_content() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn detects_modified_inputs() {
+        let mut manifest = Manifest::default();
+        manifest.record(day!(9), "1 2 3\n\n4 5 6\n");
+        manifest.record(day!(2), "x");
+
+        assert_eq!(
+            manifest.check(day!(9), "1 2 3\n\n4 5 6\n"),
+            Integrity::Intact
+        );
+        assert_eq!(
+            manifest.check(day!(9), "1 2 3\n4 5 6\n"),
+            Integrity::Modified
+        );
+        assert_eq!(manifest.check(day!(3), "x"), Integrity::Unknown);
+
+        manifest.record(day!(9), "1 2 3\n4 5 6\n");
+        assert_eq!(manifest.data.len(), 2);
+        assert_eq!(manifest.data[0].day, day!(2));
+        assert_eq!(manifest.check(day!(9), "1 2 3\n4 5 6\n"), Integrity::Intact);
+    }
+
+    #[test]
+    fn roundtrips_manifest() {
+        let mut manifest = Manifest::default();
+        manifest.record(day!(1), "input");
+
+        let json = JsonValue::from(manifest.clone()).stringify().unwrap();
+        assert_eq!(Manifest::try_from(json).unwrap(), manifest);
+    }
+}
@@ -0,0 +1,229 @@
+/// Migration of projects from the layout before multi-year support, where all data lived in `data/` and the
+/// solutions in `src/bin/NN.rs`.
+///
+/// Files of the old layout are moved to `data/<year>` and `src/bin/<year>-NN.rs` by `cargo migrate <year>`, as
+/// only the user knows which year they belong to. Other commands only point out that files of the old layout
+/// exist. Files that already exist in the new layout are never replaced.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::{Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Entries of `data/` in the old layout.
+const LEGACY_DATA: &[&str] = &[
+    "answers",
+    "baselines",
+    "examples",
+    "inputs",
+    "puzzles",
+    "submissions",
+    "timings.json",
+    "timings_history.jsonl",
+];
+
+/// Files of the old layout that are moved, as pairs of old and new path.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Plan {
+    moves: Vec<(PathBuf, PathBuf)>,
+    /// Files that are kept, because their new path exists already.
+    conflicts: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Plan {
+    fn add(&mut self, from: PathBuf, to: PathBuf) {
+        if to.exists() {
+            self.conflicts.push((from, to));
+        } else {
+            self.moves.push((from, to));
+        }
+    }
+
+    /// Add a file of the old layout, or all files in a directory of it.
+    /// Hidden files like `.keep` are skipped, as every directory of the new layout has them.
+    fn add_recursive(&mut self, from: &Path, to: &Path) {
+        if from
+            .file_name()
+            .and_then(|x| x.to_str())
+            .is_some_and(|x| x.starts_with('.'))
+        {
+            return;
+        }
+
+        if from.is_file() {
+            self.add(from.to_path_buf(), to.to_path_buf());
+        } else if let Ok(entries) = fs::read_dir(from) {
+            let mut paths: Vec<PathBuf> = entries.filter_map(|x| Some(x.ok()?.path())).collect();
+            paths.sort();
+
+            for path in paths {
+                if let Some(name) = path.file_name() {
+                    self.add_recursive(&path, &to.join(name));
+                }
+            }
+        }
+    }
+}
+
+fn plan(root: &Path, year: Year) -> Plan {
+    let mut plan = Plan::default();
+
+    let data_dir = root.join("data");
+    for name in LEGACY_DATA {
+        plan.add_recursive(&data_dir.join(name), &root.join(year.data_dir()).join(name));
+    }
+
+    let bin_dir = root.join("src").join("bin");
+    if let Ok(entries) = fs::read_dir(&bin_dir) {
+        let mut paths: Vec<PathBuf> = entries.filter_map(|x| Some(x.ok()?.path())).collect();
+        paths.sort();
+
+        for path in paths {
+            let day = path
+                .file_name()
+                .and_then(|x| x.to_str())
+                .and_then(|x| x.strip_suffix(".rs"))
+                .and_then(|x| x.parse::<Day>().ok());
+
+            if let Some(day) = day {
+                let id = PuzzleId::new(year, day);
+                plan.add(path, bin_dir.join(format!("{id}.rs")));
+            }
+        }
+    }
+
+    plan
+}
+
+fn apply(plan: &Plan) -> Result<(), io::Error> {
+    for (from, to) in &plan.moves {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(from, to)?;
+    }
+
+    // remove the directories of the old layout that are empty now, stopping at the first one that is not.
+    for (from, _) in &plan.moves {
+        for dir in from.ancestors().skip(1) {
+            if fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the files of the old layout in the project root and move them to the layout of a year.
+/// With `dry_run`, the files are only printed.
+pub fn handle(year: Year, dry_run: bool) {
+    let plan = plan(Path::new("."), year);
+
+    if plan.moves.is_empty() && plan.conflicts.is_empty() {
+        println!("No files of the old layout found.");
+        return;
+    }
+
+    for (from, to) in &plan.moves {
+        println!("{} → {}", from.display(), to.display());
+    }
+
+    for (from, to) in &plan.conflicts {
+        eprintln!(
+            "Warning: `{}` is left in the old layout, as `{}` exists already.",
+            from.display(),
+            to.display()
+        );
+    }
+
+    if dry_run || plan.moves.is_empty() {
+        return;
+    }
+
+    match apply(&plan) {
+        Ok(()) => println!(
+            "{ANSI_BOLD}Moved {} files of the old layout to `data/{year}` and `src/bin/{year}-NN.rs`.{ANSI_RESET}",
+            plan.moves.len()
+        ),
+        Err(e) => {
+            eprintln!("Failed to move files to the layout of {year}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Point out files of the old layout in the project root, without touching them.
+pub fn warn_legacy() {
+    let plan = plan(Path::new("."), Year::current());
+
+    let count = plan.moves.len() + plan.conflicts.len();
+    if count > 0 {
+        eprintln!(
+            "{ANSI_ITALIC}Found {count} files of the old layout, run `cargo migrate <year>` to move them to the layout of that year.{ANSI_RESET}"
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{apply, plan};
+    use crate::template::Year;
+    use std::{env, fs, path::Path};
+
+    fn write(root: &Path, path: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "content").unwrap();
+    }
+
+    #[test]
+    fn moves_old_layout() {
+        let root = env::temp_dir().join(format!("aoc-migrate-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        for path in [
+            "data/inputs/01.txt",
+            "data/inputs/02.txt",
+            "data/inputs/.keep",
+            "data/examples/15/small.txt",
+            "data/timings.json",
+            "data/2024/inputs/02.txt",
+            "src/bin/01.rs",
+            "src/bin/2024-02.rs",
+        ] {
+            write(&root, path);
+        }
+
+        let plan = plan(&root, Year::new(2024).unwrap());
+        let targets: Vec<String> = plan
+            .moves
+            .iter()
+            .map(|(_, to)| to.strip_prefix(&root).unwrap().display().to_string())
+            .collect();
+
+        assert_eq!(
+            targets,
+            [
+                "data/2024/examples/15/small.txt",
+                "data/2024/inputs/01.txt",
+                "data/2024/timings.json",
+                "src/bin/2024-01.rs",
+            ]
+        );
+        assert_eq!(plan.conflicts.len(), 1);
+        assert!(plan.conflicts[0].0.ends_with("data/inputs/02.txt"));
+
+        apply(&plan).unwrap();
+        assert!(root.join("data/2024/inputs/01.txt").exists());
+        assert!(!root.join("src/bin/01.rs").exists());
+        assert!(!root.join("data/examples").exists());
+        assert!(root.join("data/inputs/02.txt").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,44 +1,81 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod export;
+pub mod history;
+pub mod input;
+pub mod migrate;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod solution_args;
+pub mod stats;
 
 pub use day::*;
+pub use year::*;
 
+mod answers;
+mod config;
 mod day;
+mod diff;
+mod html;
+mod manifest;
+mod progress;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of a puzzle to a string, e.g. `data/2024/examples/01.txt`.
+/// Pass a [`Day`] to read the file of the current year.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, id: impl Into<PuzzleId>) -> String {
+    let id = id.into();
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(id.year.data_dir())
+        .join(folder)
+        .join(format!("{}.txt", id.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, id: impl Into<PuzzleId>, part: u8) -> String {
+    let id = id.into();
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(id.year.data_dir())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", id.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Path of the solution binary of a puzzle, relative to the project root.
+/// Pass a [`Day`] to get the binary of the current year.
+#[must_use]
+pub fn get_path_for_bin(id: impl Into<PuzzleId>) -> String {
+    format!("./src/bin/{}.rs", id.into())
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Passing `parse` (e.g. `solution!(8, parse)` or `solution!(8, parse, 1)`) runs a `parse` function once,
+/// timed as its own phase, and hands a reference to its output to each part instead of the raw input.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +87,97 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse) => {
+        $crate::solution!(@impl_parsed $day, parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse, 1) => {
+        $crate::solution!(@impl_parsed $day, parse, [part_one, 1]);
+    };
+    ($day:expr, parse, 2) => {
+        $crate::solution!(@impl_parsed $day, parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            apply_memory_limit();
+            let input = $crate::template::input::read_input(DAY);
+            $( run_part($func, &input, DAY, $part); )*
+        }
+
+        /// Entry of this day in the in-process solution registry.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::DaySolution =
+            $crate::template::registry::DaySolution {
+                day: DAY,
+                parts: &[$( $part ),*],
+                run: |input, is_timed, on_report| {
+                    use $crate::template::runner::*;
+                    $( on_report(solve_part($func, input, DAY, $part, is_timed)); )*
+                },
+            };
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            apply_memory_limit();
+            let input = $crate::template::input::read_input(DAY);
+            let parsed = run_parse($parse, &input, DAY);
+            // NOTE: parts are called through a closure, so they can take e.g. `&[T]` for a parsed `Vec<T>`.
+            $( run_part(|parsed| $func(parsed), &parsed, DAY, $part); )*
+        }
+
+        /// Entry of this day in the in-process solution registry.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::DaySolution =
+            $crate::template::registry::DaySolution {
+                day: DAY,
+                parts: &[$( $part ),*],
+                run: |input, is_timed, on_report| {
+                    use $crate::template::runner::*;
+                    let (parsed, report) = solve_parse($parse, input, DAY, is_timed);
+                    on_report(report);
+                    if let Some(parsed) = parsed {
+                        $( on_report(solve_part(|parsed| $func(parsed), &parsed, DAY, $part, is_timed)); )*
+                    }
+                },
+            };
+    };
+
+    (@header $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
+    };
+}
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
+/// Generates one test per example of the current day, see [`examples`].
+///
+/// The tests are generated by `build.rs` for every example found in `data/<year>/examples` and check the
+/// day's parts against the example's expected answers. Use it as the test module of a solution:
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     advent_of_code::example_tests!();
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        include!(concat!(
+            env!("OUT_DIR"),
+            "/examples/",
+            env!("CARGO_BIN_NAME"),
+            ".rs"
+        ));
     };
 }
@@ -0,0 +1,343 @@
+/// Progress of the event, derived from the files in the project.
+///
+/// A star counts as earned once the website accepted an answer for its part, either as logged in the submissions
+/// or as confirmed in the answer registry, or reported the part as solved already. Answers pinned with
+/// `cargo verify --pin` are not confirmed by the website, so they do not count.
+/// The README can hold a calendar of the progress between two `<!--- progress --->` comments.
+use std::{fs, path::Path};
+
+use crate::template::{
+    answers::Answers,
+    aoc_client::get_input_path,
+    examples, get_path_for_bin,
+    history::History,
+    readme_benchmarks::{locate_table, Error},
+    registry,
+    submissions::Submissions,
+    timings::{format_nanos, Timing, Timings},
+    Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, ANSI_YELLOW,
+};
+
+pub static MARKER: &str = "<!--- progress --->";
+
+/// Profile that benchmarks are recorded with.
+const PROFILE: &str = "release";
+
+/// Days per row of the calendar.
+const WEEK: usize = 5;
+
+/// Width of a calendar cell in the terminal, in characters.
+const CELL_WIDTH: usize = 12;
+
+/// Progress of a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayProgress {
+    pub day: Day,
+    /// Number of parts whose star was earned, see [`earned_parts`].
+    pub stars: u8,
+    /// Parts the day has, e.g. only part one on the last day of an event.
+    pub parts: &'static [u8],
+    /// Whether `src/bin/<year>-NN.rs` exists.
+    pub scaffolded: bool,
+    /// Whether the puzzle input exists and is not empty.
+    pub has_input: bool,
+    /// Whether any example exists and is not empty.
+    pub has_example: bool,
+    /// Total time of the latest benchmark, as nanos.
+    pub last_timing: Option<f64>,
+}
+
+fn is_non_empty_file(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|x| x.is_file() && x.len() > 0)
+}
+
+fn has_example(day: Day) -> bool {
+    let default = Year::current()
+        .data_dir()
+        .join("examples")
+        .join(format!("{day}.txt"));
+
+    is_non_empty_file(&default)
+        || fs::read_dir(examples::get_dir(day)).is_ok_and(|entries| {
+            entries.filter_map(Result::ok).any(|entry| {
+                let path = entry.path();
+                path.extension().is_some_and(|x| x == "txt") && is_non_empty_file(&path)
+            })
+        })
+}
+
+/// Parts of a day whose star was earned on the website.
+pub fn earned_parts(day: Day) -> Vec<u8> {
+    let submissions = Submissions::read_from_file(day).unwrap_or_default();
+    let answers = Answers::read_from_file(day).unwrap_or_default();
+    earned(registry::parts(day), &submissions, &answers)
+}
+
+fn earned(parts: &[u8], submissions: &Submissions, answers: &Answers) -> Vec<u8> {
+    parts
+        .iter()
+        .copied()
+        .filter(|part| submissions.is_solved(*part) || answers.is_confirmed(*part))
+        .collect()
+}
+
+impl DayProgress {
+    /// Collect the progress of a day from the files in the project.
+    /// The latest timing is taken from the benchmark history, falling back to the stored timings.
+    pub fn read(day: Day, history: &History, timings: &Timings) -> Self {
+        let stars = earned_parts(day).len();
+
+        let last_timing = history
+            .latest(day, PROFILE)
+            .map(|(_, timing)| timing)
+            .or_else(|| timings.data.iter().find(|x| x.day == day))
+            .map(|timing: &Timing| timing.total_nanos)
+            .filter(|x| *x > 0.0);
+
+        DayProgress {
+            day,
+            #[allow(clippy::cast_possible_truncation)]
+            stars: stars as u8,
+            parts: registry::parts(day),
+            scaffolded: Path::new(&get_path_for_bin(day)).exists(),
+            has_input: is_non_empty_file(Path::new(&get_input_path(day))),
+            has_example: has_example(day),
+            last_timing,
+        }
+    }
+}
+
+pub fn total_stars(days: &[DayProgress]) -> usize {
+    days.iter().map(|x| usize::from(x.stars)).sum()
+}
+
+/// Number of stars that can be earned on the days.
+fn max_stars(days: &[DayProgress]) -> usize {
+    days.iter().map(|x| x.parts.len()).sum()
+}
+
+fn format_stars(day: &DayProgress) -> String {
+    (0..day.parts.len())
+        .map(|i| {
+            if i < usize::from(day.stars) {
+                '★'
+            } else {
+                '☆'
+            }
+        })
+        .collect()
+}
+
+/// Pad a cell to its width before colors are applied, since escape codes would count towards it.
+fn pad(s: &str) -> String {
+    format!("{s:<CELL_WIDTH$}")
+}
+
+/// Render the progress as a calendar grid for the terminal.
+pub fn render_terminal(days: &[DayProgress]) -> String {
+    let mut lines = vec![];
+
+    for week in days.chunks(WEEK) {
+        let mut title = String::new();
+        let mut files = String::new();
+        let mut timing = String::new();
+
+        for day in week {
+            let stars = format_stars(day);
+            title += &format!(
+                "{ANSI_BOLD}{}{ANSI_RESET} {ANSI_YELLOW}{}{ANSI_RESET}{}",
+                day.day,
+                stars,
+                " ".repeat(CELL_WIDTH - 3 - stars.chars().count())
+            );
+
+            let flag = |is_set: bool, name: &str| {
+                if is_set {
+                    name.to_string()
+                } else {
+                    "-".repeat(name.len())
+                }
+            };
+            files += &pad(&format!(
+                "{} {} {}",
+                flag(day.scaffolded, "bin"),
+                flag(day.has_input, "in"),
+                flag(day.has_example, "ex")
+            ));
+
+            timing += &format!(
+                "{ANSI_ITALIC}{}{ANSI_RESET}",
+                pad(&day.last_timing.map_or_else(|| "-".into(), format_nanos))
+            );
+        }
+
+        lines.extend([title, files, timing, String::new()]);
+    }
+
+    lines.push(format!(
+        "{ANSI_BOLD}{}/{} stars{ANSI_RESET}",
+        total_stars(days),
+        max_stars(days)
+    ));
+
+    lines.join("\n")
+}
+
+/// Render the progress as a Markdown calendar.
+fn render_markdown(days: &[DayProgress]) -> String {
+    let columns = days.len().min(WEEK);
+
+    let mut lines = vec![
+        MARKER.to_string(),
+        "## Progress".to_string(),
+        String::new(),
+        format!("**{}/{} ⭐**", total_stars(days), max_stars(days)),
+        String::new(),
+        format!("|{}", "   |".repeat(columns)),
+        format!("|{}", " :---: |".repeat(columns)),
+    ];
+
+    for week in days.chunks(WEEK) {
+        let cells: Vec<String> = week
+            .iter()
+            .map(|day| {
+                let label = if day.scaffolded {
+                    format!("[{}]({})", day.day.into_inner(), get_path_for_bin(day.day))
+                } else {
+                    day.day.into_inner().to_string()
+                };
+
+                format!(
+                    "{label}<br>{}",
+                    "⭐".repeat(day.stars.into())
+                        + &"☆".repeat(day.parts.len().saturating_sub(day.stars.into()))
+                )
+            })
+            .collect();
+
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, days: &[DayProgress]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    s.replace_range(
+        positions.pos_start..positions.pos_end,
+        &render_markdown(days),
+    );
+    Ok(())
+}
+
+/// Update the progress section of the README.
+pub fn update_readme(days: &[DayProgress]) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, days)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{earned, render_terminal, update_content, DayProgress, MARKER};
+    use crate::{
+        day,
+        template::{
+            all_days,
+            answers::Answers,
+            get_path_for_bin,
+            submissions::{Outcome, Submission, Submissions},
+            Day,
+        },
+    };
+
+    fn progress(day: Day) -> DayProgress {
+        let n = day.into_inner();
+        DayProgress {
+            day,
+            stars: match n {
+                1..=3 => 2,
+                4 => 1,
+                _ => 0,
+            },
+            parts: if n == 25 { &[1] } else { &[1, 2] },
+            scaffolded: n <= 4,
+            has_input: n <= 4,
+            has_example: n <= 2,
+            last_timing: (n <= 3).then_some(1_500_000.0),
+        }
+    }
+
+    fn get_mock_progress() -> Vec<DayProgress> {
+        all_days().map(progress).collect()
+    }
+
+    #[test]
+    fn renders_calendar_grid() {
+        let out = render_terminal(&get_mock_progress());
+        let lines: Vec<&str> = out.lines().collect();
+
+        // 5 weeks of 3 lines and a separator, followed by the total.
+        assert_eq!(lines.len(), 21);
+        assert!(lines[1].starts_with("bin in ex   bin in ex   bin in --   bin in --   --- -- --"));
+        assert!(lines[2].contains("1.5ms"));
+        assert!(lines[20].contains("7/49 stars"));
+        assert!(lines[16].contains("25\x1b[0m \x1b[33m☆\x1b[0m "));
+    }
+
+    #[test]
+    fn updates_readme_section() {
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbar");
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        update_content(&mut s, &get_mock_progress()).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert!(s.contains("**7/49 ⭐**"));
+        assert!(s.contains("| 25<br>☆ |"));
+        assert!(s.contains(&format!("| [1]({})<br>⭐⭐ |", get_path_for_bin(day!(1)))));
+        assert!(s.contains(&format!(
+            "| [4]({})<br>⭐☆ | 5<br>☆☆ |",
+            get_path_for_bin(day!(4))
+        )));
+        assert!(s.ends_with(&format!("{MARKER}\nbar")));
+    }
+
+    #[test]
+    fn counts_earned_stars() {
+        let submission = |part: u8, outcome: Outcome| Submission {
+            timestamp: 1_733_000_000,
+            part,
+            answer: "1".into(),
+            outcome,
+        };
+
+        let submissions = Submissions {
+            entries: vec![submission(1, Outcome::AlreadySolved)],
+        };
+        assert_eq!(earned(&[1, 2], &submissions, &Answers::default()), vec![1]);
+
+        // NOTE: answers pinned with `verify --pin` are not confirmed.
+        let mut answers = Answers::default();
+        answers.input.set(1, Some("1".into()));
+        answers.input.set(2, Some("2".into()));
+        assert!(earned(&[1, 2], &Submissions::default(), &answers).is_empty());
+
+        answers.confirm(2, "2");
+        assert_eq!(earned(&[1, 2], &Submissions::default(), &answers), vec![2]);
+        assert_eq!(
+            earned(&[1], &Submissions::default(), &answers),
+            vec![] as Vec<u8>
+        );
+    }
+
+    #[test]
+    fn errors_without_readme_section() {
+        let mut s = "# readme".to_string();
+        assert!(update_content(&mut s, &get_mock_progress()).is_err());
+    }
+}
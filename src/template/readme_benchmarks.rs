@@ -1,11 +1,37 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// The tables are configured in the `[[readme]]` section of `aoc.toml`, one entry per table:
+///
+/// ```toml
+/// [[readme]]
+/// marker = "benchmarking table"   # the table goes between two `<!--- benchmarking table --->` comments
+/// title = "Benchmarks"
+/// header_level = 2
+/// columns = ["parse", "part_1", "part_2", "part_1_p95", "stars", "verified", "bar"]
+/// metric = "median"               # defaults to the `--median` flag of `cargo time`
+/// totals = true
+/// year = 2024                     # only updated by `cargo time` for this year, defaults to any year
+/// ```
+///
+/// `stars` and `verified` use the same stars as `cargo status`, i.e. only parts the website accepted.
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    str::FromStr,
+};
+use toml::Value;
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::config::Config;
+use crate::template::progress;
+use crate::template::report::PartReport;
+use crate::template::timings::{format_nanos, PartTiming, Timing, Timings};
+use crate::template::{get_path_for_bin, runner::part_label, Day, PuzzleId, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+static DEFAULT_MARKER: &str = "benchmarking table";
+
+/// Width of the bars in the `bar` column, in characters.
+const BAR_WIDTH: f64 = 10.0;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -20,23 +46,147 @@ impl From<std::io::Error> for Error {
     }
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+/// Which statistic is shown for each part in the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimingMetric {
+    #[default]
+    Mean,
+    Median,
+}
+
+impl FromStr for TimingMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mean" => Ok(TimingMetric::Mean),
+            "median" => Ok(TimingMetric::Median),
+            x => Err(format!(
+                "unknown timing metric `{x}`, expecting `mean` or `median`."
+            )),
+        }
+    }
+}
+
+/// A column of the benchmark table. Per-part columns also apply to the parse phase ([`PartReport::PARSE`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Time of a part in the table's metric, e.g. `part_1` or `parse`.
+    Time(u8),
+    /// Median time of a part, e.g. `part_1_median`.
+    Median(u8),
+    /// 95th percentile of a part, e.g. `part_1_p95`.
+    P95(u8),
+    /// Number of samples of a part, e.g. `part_1_samples`.
+    Samples(u8),
+    /// Checkmark if every solved part earned its star on the website. Answers pinned with `cargo verify --pin`
+    /// do not count.
+    Verified,
+    /// One star per solved part.
+    Stars,
+    /// Bar of the day's total time, relative to the slowest day.
+    Bar,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let part = |name: &str| match name {
+            "parse" => Some(PartReport::PARSE),
+            "part_1" => Some(1),
+            "part_2" => Some(2),
+            _ => None,
+        };
+
+        let column = match s {
+            "verified" => Some(Column::Verified),
+            "stars" => Some(Column::Stars),
+            "bar" => Some(Column::Bar),
+            _ => {
+                if let Some(name) = s.strip_suffix("_median") {
+                    part(name).map(Column::Median)
+                } else if let Some(name) = s.strip_suffix("_p95") {
+                    part(name).map(Column::P95)
+                } else if let Some(name) = s.strip_suffix("_samples") {
+                    part(name).map(Column::Samples)
+                } else {
+                    part(s).map(Column::Time)
+                }
+            }
+        };
+
+        column.ok_or_else(|| format!("unknown column `{s}`."))
+    }
+}
+
+/// Configuration of a single table in the README.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableConfig {
+    /// Text of the HTML comments that delimit the table.
+    pub marker: String,
+    pub title: String,
+    pub header_level: u8,
+    pub columns: Vec<Column>,
+    /// Overrides the metric that `cargo time` was called with.
+    pub metric: Option<TimingMetric>,
+    /// Add a row with the sum of each column.
+    pub totals: bool,
+    /// Year whose timings the table shows. Tables without a year show the year `cargo time` ran for.
+    pub year: Option<Year>,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        TableConfig {
+            marker: DEFAULT_MARKER.into(),
+            title: "Benchmarks".into(),
+            header_level: 2,
+            columns: vec![Column::Time(1), Column::Time(2)],
+            metric: None,
+            totals: false,
+            year: None,
+        }
+    }
+}
+
+impl TableConfig {
+    fn marker(&self) -> String {
+        format!("<!--- {} --->", self.marker)
+    }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Read the table configs from the `[[readme]]` section. Defaults to a single table without config.
+pub fn read_table_configs(config: &Config) -> Result<Vec<TableConfig>, String> {
+    let tables = match config.section("readme") {
+        None => return Ok(vec![TableConfig::default()]),
+        Some(Value::Array(tables)) => tables
+            .iter()
+            .map(TableConfig::try_from)
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(table) => vec![TableConfig::try_from(table)?],
+    };
+
+    let markers: HashSet<&str> = tables.iter().map(|x| x.marker.as_str()).collect();
+    if markers.len() != tables.len() {
+        return Err("every table in `readme` needs a distinct `marker`.".into());
+    }
+
+    Ok(tables)
+}
+
+pub struct TablePosition {
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
@@ -52,115 +202,427 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn format_cell(part: Option<&PartTiming>, metric: TimingMetric) -> String {
+    match (metric, part) {
+        (_, None) => "-".into(),
+        (TimingMetric::Median, Some(part)) if part.is_solved() => part
+            .stats
+            .as_ref()
+            .map_or_else(|| part.format(), |stats| format!("{:.1?}", stats.median())),
+        (_, Some(part)) => part.format(),
+    }
+}
+
+/// Time of a day in a metric as nanos.
+fn day_nanos(timing: &Timing, metric: TimingMetric) -> f64 {
+    match metric {
+        TimingMetric::Mean => timing.total_nanos,
+        TimingMetric::Median => timing.median_nanos(),
+    }
+}
+
+/// Time of a solved part in a metric as nanos.
+fn part_nanos(timing: &Timing, part: u8, metric: TimingMetric) -> Option<f64> {
+    let part = timing.part(part).filter(|x| x.is_solved())?;
+
+    Some(match metric {
+        TimingMetric::Mean => part.nanos,
+        TimingMetric::Median => part.median_nanos(),
+    })
+}
+
+/// Parts of each day whose star was earned on the website, as counted by [`progress::earned_parts`].
+type EarnedParts = HashMap<Day, Vec<u8>>;
+
+fn count_stars(timing: &Timing, earned: &EarnedParts) -> usize {
+    earned.get(&timing.day).map_or(0, Vec::len)
+}
+
+/// Whether every solved part of a day earned its star.
+fn is_verified(timing: &Timing, earned: &EarnedParts) -> bool {
+    let earned = earned.get(&timing.day).map_or(&[][..], Vec::as_slice);
+    let solved: Vec<u8> = [1, 2]
+        .into_iter()
+        .filter(|part| timing.part(*part).is_some_and(PartTiming::is_solved))
+        .collect();
+
+    !solved.is_empty() && solved.iter().all(|part| earned.contains(part))
+}
+
+fn header_cell(column: Column, metric: TimingMetric) -> String {
+    match column {
+        Column::Time(part) if metric == TimingMetric::Median => {
+            format!("{} (median)", part_label(part))
+        }
+        Column::Time(part) => part_label(part),
+        Column::Median(part) => format!("{} (median)", part_label(part)),
+        Column::P95(part) => format!("{} (p95)", part_label(part)),
+        Column::Samples(part) => format!("{} samples", part_label(part)),
+        Column::Verified => "Verified".into(),
+        Column::Stars => "Stars".into(),
+        Column::Bar => "Relative".into(),
+    }
+}
+
+fn body_cell(
+    column: Column,
+    timing: &Timing,
+    metric: TimingMetric,
+    earned: &EarnedParts,
+    max_nanos: f64,
+) -> String {
+    let code = |x: String| format!("`{x}`");
+
+    match column {
+        Column::Time(part) => code(format_cell(timing.part(part), metric)),
+        Column::Median(part) => code(
+            part_nanos(timing, part, TimingMetric::Median).map_or_else(|| "-".into(), format_nanos),
+        ),
+        Column::P95(part) => code(
+            timing
+                .stats(part)
+                .map_or_else(|| "-".into(), |stats| format!("{:.1?}", stats.p95())),
+        ),
+        Column::Samples(part) => timing
+            .part(part)
+            .map(|x| x.samples)
+            .filter(|x| *x > 0)
+            .map_or_else(|| "-".into(), |x| x.to_string()),
+        Column::Verified => {
+            if is_verified(timing, earned) {
+                "✔".into()
+            } else {
+                String::new()
+            }
+        }
+        Column::Stars => "⭐".repeat(count_stars(timing, earned)),
+        Column::Bar => {
+            let nanos = day_nanos(timing, metric);
+            if max_nanos > 0.0 && nanos > 0.0 {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let width = (nanos / max_nanos * BAR_WIDTH).round().max(1.0) as usize;
+                "█".repeat(width)
+            } else {
+                String::new()
+            }
+        }
+    }
+}
+
+fn total_cell(
+    column: Column,
+    timings: &Timings,
+    metric: TimingMetric,
+    earned: &EarnedParts,
+) -> String {
+    let sum = |metric: TimingMetric, part: u8| -> f64 {
+        timings
+            .data
+            .iter()
+            .filter_map(|timing| part_nanos(timing, part, metric))
+            .sum()
+    };
+
+    match column {
+        Column::Time(part) => format!("**{}**", format_nanos(sum(metric, part))),
+        Column::Median(part) => format!("**{}**", format_nanos(sum(TimingMetric::Median, part))),
+        Column::Stars => format!(
+            "**{}**",
+            timings
+                .data
+                .iter()
+                .map(|timing| count_stars(timing, earned))
+                .sum::<usize>()
+        ),
+        Column::P95(_) | Column::Samples(_) | Column::Verified | Column::Bar => String::new(),
+    }
+}
+
+fn construct_table(
+    config: &TableConfig,
+    timings: &Timings,
+    metric: TimingMetric,
+    earned: &EarnedParts,
+) -> String {
+    let metric = config.metric.unwrap_or(metric);
+    let marker = config.marker();
+    let year = config.year.unwrap_or_else(Year::current);
+
+    let header = format!(
+        "{} {}",
+        "#".repeat(config.header_level.into()),
+        config.title
+    );
+
+    let columns: Vec<String> = config
+        .columns
+        .iter()
+        .map(|column| header_cell(*column, metric))
+        .collect();
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day | {} |", columns.join(" | ")),
+        format!("|{}", " :---: |".repeat(columns.len() + 1)),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|timing| day_nanos(timing, metric))
+        .fold(0.0, f64::max);
+
+    for timing in &timings.data {
+        let path = get_path_for_bin(PuzzleId::new(year, timing.day));
+        let cells: Vec<String> = config
+            .columns
+            .iter()
+            .map(|column| body_cell(*column, timing, metric, earned, max_nanos))
+            .collect();
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cells.join(" | ")
         ));
     }
 
+    if config.totals {
+        let cells: Vec<String> = config
+            .columns
+            .iter()
+            .map(|column| total_cell(*column, timings, metric, earned))
+            .collect();
+
+        lines.push(format!("| **Total** | {} |", cells.join(" | ")));
+    }
+
+    let total_millis = match metric {
+        TimingMetric::Mean => timings.total_millis(),
+        TimingMetric::Median => timings.total_median_millis(),
+    };
+
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    config: &TableConfig,
+    timings: &Timings,
+    metric: TimingMetric,
+    earned: &EarnedParts,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &config.marker())?;
+    let table = construct_table(config, timings, metric, earned);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+pub fn update(timings: Timings, metric: TimingMetric) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+
+    let configs = Config::read_from_file()
+        .and_then(|config| read_table_configs(&config))
+        .map_err(Error::Parser)?;
+
+    let earned: EarnedParts = timings
+        .data
+        .iter()
+        .map(|timing| (timing.day, progress::earned_parts(timing.day)))
+        .collect();
+
+    let year = Year::current();
+    for config in configs.iter().filter(|x| x.year.is_none_or(|y| y == year)) {
+        update_content(&mut readme, config, &timings, metric, &earned)?;
+    }
+
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&Value> for TableConfig {
+    type Error = String;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let table = value.as_table().ok_or("Expected readme to be a table.")?;
+
+        let mut config = TableConfig::default();
+
+        if let Some(v) = table.get("marker") {
+            config.marker = v
+                .as_str()
+                .ok_or("Expected readme.marker to be a string.")?
+                .into();
+        }
+
+        if let Some(v) = table.get("title") {
+            config.title = v
+                .as_str()
+                .ok_or("Expected readme.title to be a string.")?
+                .into();
+        }
+
+        if let Some(v) = table.get("header_level") {
+            config.header_level = v
+                .as_integer()
+                .and_then(|x| u8::try_from(x).ok())
+                .filter(|x| (1..=6).contains(x))
+                .ok_or("Expected readme.header_level to be a number between 1 and 6.")?;
+        }
+
+        if let Some(v) = table.get("columns") {
+            config.columns = v
+                .as_array()
+                .ok_or("Expected readme.columns to be an array.")?
+                .iter()
+                .map(|x| {
+                    x.as_str()
+                        .ok_or_else(|| "Expected readme.columns to contain strings.".to_string())
+                        .and_then(Column::from_str)
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(v) = table.get("metric") {
+            config.metric = Some(
+                v.as_str()
+                    .ok_or("Expected readme.metric to be a string.")?
+                    .parse()?,
+            );
+        }
+
+        if let Some(v) = table.get("totals") {
+            config.totals = v
+                .as_bool()
+                .ok_or("Expected readme.totals to be a boolean.")?;
+        }
+
+        if let Some(v) = table.get("year") {
+            config.year = Some(
+                v.as_integer()
+                    .and_then(|x| u16::try_from(x).ok())
+                    .and_then(Year::new)
+                    .ok_or("Expected readme.year to be a year of advent.")?,
+            );
+        }
+
+        Ok(config)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{
+        read_table_configs, update_content, Column, TableConfig, TimingMetric, DEFAULT_MARKER,
+    };
+    use crate::{
+        day,
+        template::config::Config,
+        template::report::PartStatus,
+        template::stats::BenchStats,
+        template::timings::{PartTiming, Timing, Timings},
+        template::{Day, Year},
+    };
+    use std::{collections::HashMap, time::Duration};
+
+    fn solved(nanos: f64) -> PartTiming {
+        PartTiming {
+            status: PartStatus::Solved,
+            nanos,
+            samples: 1,
+            stats: None,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse: None,
+                    part_1: Some(solved(10e6)),
+                    part_2: Some(solved(20e6)),
+                    total_nanos: 3e+7,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse: None,
+                    part_1: Some(solved(30e6)),
+                    part_2: Some(solved(40e6)),
+                    total_nanos: 7e+7,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    parse: None,
+                    part_1: Some(solved(40e6)),
+                    part_2: Some(solved(50e6)),
+                    total_nanos: 9e+7,
                 },
             ],
         }
     }
 
+    fn marker() -> String {
+        TableConfig::default().marker()
+    }
+
+    fn config() -> TableConfig {
+        TableConfig {
+            year: Year::new(2024),
+            ..TableConfig::default()
+        }
+    }
+
+    fn update(s: &mut String, timings: &Timings, metric: TimingMetric) -> Result<(), super::Error> {
+        update_content(s, &config(), timings, metric, &HashMap::new())
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, &get_mock_timings(), TimingMetric::Mean).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("{} {} {}", marker(), marker(), marker());
+        update(&mut s, &get_mock_timings(), TimingMetric::Mean).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker(), marker());
+        update(&mut s, &get_mock_timings(), TimingMetric::Mean).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker(), marker());
+        update(&mut s, &get_mock_timings(), TimingMetric::Mean).unwrap();
+        update(&mut s, &get_mock_timings(), TimingMetric::Mean).unwrap();
+        assert_eq!(s.matches(&marker()).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", marker(), marker());
+        update(&mut s, &get_mock_timings(), TimingMetric::Mean).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,10 +630,10 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +642,103 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_median_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().stats = Some(BenchStats::from_durations(&[
+            Duration::from_millis(8),
+            Duration::from_millis(9),
+            Duration::from_millis(10),
+        ]));
+
+        let mut s = format!("{}{}", marker(), marker());
+        update(&mut s, &timings, TimingMetric::Median).unwrap();
+        assert!(s.contains("| Day | Part 1 (median) | Part 2 (median) |"));
+        assert!(s.contains("| [Day 1](./src/bin/2024-01.rs) | `9.0ms` | `20.0ms` |"));
+    }
+
+    #[test]
+    fn format_configured_columns() {
+        let config = TableConfig {
+            header_level: 3,
+            columns: vec![
+                Column::Time(1),
+                Column::Samples(2),
+                Column::Stars,
+                Column::Verified,
+                Column::Bar,
+            ],
+            totals: true,
+            ..config()
+        };
+
+        let mut timings = get_mock_timings();
+        timings.data[1].part_2 = None;
+
+        let mut s = format!("{}{}", marker(), marker());
+        // NOTE: stars come from the website, not from solved timings.
+        let earned: HashMap<Day, Vec<u8>> = [(day!(1), vec![1, 2]), (day!(4), vec![1])].into();
+        update_content(&mut s, &config, &timings, TimingMetric::Mean, &earned).unwrap();
+
+        assert!(s.contains("### Benchmarks"));
+        assert!(s.contains("| Day | Part 1 | Part 2 samples | Stars | Verified | Relative |"));
+        assert!(s.contains("| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | 1 | ⭐⭐ | ✔ | ███ |"));
+        assert!(s.contains("| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | - |  |  | ████████ |"));
+        assert!(s.contains("| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | 1 | ⭐ |  | ██████████ |"));
+        assert!(s.contains("| **Total** | **80.0ms** |  | **3** |  |  |"));
+    }
+
+    #[test]
+    fn updates_multiple_tables() {
+        let config = Config::try_from(
+            r#"
+            [[readme]]
+            title = "Mean"
+
+            [[readme]]
+            marker = "median table"
+            title = "Median"
+            metric = "median"
+            columns = ["part_1", "part_1_p95"]
+            year = 2023
+            "#,
+        )
+        .unwrap();
+
+        let configs = read_table_configs(&config).unwrap();
+        assert_eq!(configs.len(), 2);
+        assert_eq!(configs[0].marker, DEFAULT_MARKER);
+        assert_eq!(configs[1].columns, vec![Column::Time(1), Column::P95(1)]);
+        assert_eq!(configs[1].year, Year::new(2023));
+
+        let mut s = format!("{0}{0}\n{1}{1}", configs[0].marker(), configs[1].marker());
+        for config in &configs {
+            update_content(
+                &mut s,
+                config,
+                &get_mock_timings(),
+                TimingMetric::Mean,
+                &HashMap::new(),
+            )
+            .unwrap();
+        }
+
+        assert!(s.contains("## Mean\n\n| Day | Part 1 | Part 2 |"));
+        assert!(s.contains("## Median\n\n| Day | Part 1 (median) | Part 1 (p95) |"));
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        for content in [
+            "[readme]\ncolumns = [\"part_3\"]",
+            "[readme]\nheader_level = 7",
+            "[readme]\nmetric = \"p99\"",
+            "[readme]\nyear = 2014",
+            "[[readme]]\n[[readme]]",
+        ] {
+            let config = Config::try_from(content).unwrap();
+            assert!(read_table_configs(&config).is_err(), "{content}");
+        }
+    }
 }
@@ -0,0 +1,118 @@
+/// In-process registry of the solutions in `src/bin`.
+///
+/// The `solution!` macro exposes every day as a [`DaySolution`]. The main binary includes all solutions as
+/// modules (see `build.rs`) and registers them with their year on startup, so `all` and `time` can run days
+/// without spawning `cargo run` for each of them.
+use std::sync::OnceLock;
+
+use crate::template::{input::InputSource, report::PartReport, runner::catch_panic, Day, Year};
+
+/// Entry point of a day's solution, as generated by the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct DaySolution {
+    pub day: Day,
+    /// Parts the day has, e.g. only part one on the last day of an event.
+    pub parts: &'static [u8],
+    /// Runs all parts of the day on an input and hands over the report of each part as soon as it is done.
+    /// Parts are benched if `is_timed` is set.
+    pub run: fn(input: &str, is_timed: bool, on_report: &mut dyn FnMut(PartReport)),
+}
+
+impl DaySolution {
+    /// Run all parts of the day on the input of a source.
+    pub fn solve(
+        &self,
+        source: &InputSource,
+        is_timed: bool,
+        on_report: impl FnMut(PartReport),
+    ) -> Result<(), String> {
+        let input = source.read(self.day)?;
+        self.solve_input(&input, is_timed, on_report)
+    }
+
+    /// Run all parts of the day on an input.
+    /// Panics outside of the parts, e.g. in the solution's setup, are returned as an error.
+    pub fn solve_input(
+        &self,
+        input: &str,
+        is_timed: bool,
+        mut on_report: impl FnMut(PartReport),
+    ) -> Result<(), String> {
+        catch_panic(|| (self.run)(input, is_timed, &mut on_report))
+    }
+}
+
+static REGISTRY: OnceLock<&'static [(Year, DaySolution)]> = OnceLock::new();
+
+/// Register the solutions that can be run in-process. Only the first call has an effect.
+pub fn register(solutions: &'static [(Year, DaySolution)]) {
+    let _ = REGISTRY.set(solutions);
+}
+
+/// Get the registered solution of a day in the current year, if any.
+pub fn get(day: Day) -> Option<&'static DaySolution> {
+    let year = Year::current();
+    REGISTRY
+        .get()?
+        .iter()
+        .find(|(y, solution)| *y == year && solution.day == day)
+        .map(|(_, solution)| solution)
+}
+
+/// Parts of a day in the current year. Days that are not registered are assumed to have both parts.
+pub fn parts(day: Day) -> &'static [u8] {
+    get(day).map_or(&[1, 2], |solution| solution.parts)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySolution;
+    use crate::{
+        day,
+        template::{report::PartStatus, runner::solve_part},
+    };
+
+    #[test]
+    fn collects_part_reports() {
+        let solution = DaySolution {
+            day: day!(1),
+            parts: &[1, 2],
+            run: |input, is_timed, on_report| {
+                on_report(solve_part(
+                    |x: &str| Some(x.len()),
+                    input,
+                    day!(1),
+                    1,
+                    is_timed,
+                ));
+                on_report(solve_part(
+                    |_: &str| None::<u32>,
+                    input,
+                    day!(1),
+                    2,
+                    is_timed,
+                ));
+            },
+        };
+
+        let mut reports = vec![];
+        let result = solution.solve_input("abc", false, |r| reports.push(r));
+        assert_eq!(result, Ok(()));
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].answer, Some("3".into()));
+        assert_eq!(reports[0].status, PartStatus::Solved);
+        assert_eq!(reports[1].status, PartStatus::Unsolved);
+    }
+
+    #[test]
+    fn reports_panics_outside_of_parts() {
+        let solution = DaySolution {
+            day: day!(1),
+            parts: &[1, 2],
+            run: |_, _, _| panic!("oops"),
+        };
+
+        let result = solution.solve_input("abc", false, |_| {});
+        assert!(result.unwrap_err().ends_with(": oops"));
+    }
+}
@@ -0,0 +1,408 @@
+/// Structured, machine-readable records emitted by solution binaries.
+///
+/// When a day binary is invoked with `--format json`, it writes a [`Record`] when a part starts and when it
+/// finishes instead of the human-readable output, each as a JSON object on its own line:
+///
+/// ```json
+/// {"type":"started","part":1}
+/// {"type":"report","day":"01","part":1,"answer":"42","status":"solved","error":null,"stats":{...}}
+/// ```
+///
+/// `run_multi` consumes these records instead of scraping text.
+use std::{collections::HashMap, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{stats::BenchStats, Day};
+
+/// Output format of a solution binary.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            x => Err(format!(
+                "unknown output format `{x}`, expecting `human` or `json`."
+            )),
+        }
+    }
+}
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+    /// The part panicked.
+    Panicked,
+    /// The part exceeded its time limit and was killed.
+    Timeout,
+}
+
+impl PartStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Failed => "failed",
+            PartStatus::Panicked => "panicked",
+            PartStatus::Timeout => "timeout",
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "failed" => Ok(PartStatus::Failed),
+            "panicked" => Ok(PartStatus::Panicked),
+            "timeout" => Ok(PartStatus::Timeout),
+            x => Err(format!("unknown part status `{x}`.")),
+        }
+    }
+}
+
+/// Result of running (and optionally benching) a single part of a day.
+/// The shared parse phase is reported as part [`PartReport::PARSE`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: PartStatus,
+    /// Error or panic message of a failed part.
+    pub error: Option<String>,
+    pub stats: BenchStats,
+}
+
+impl PartReport {
+    /// Part number used for the parse phase of solutions declared with `solution!(N, parse)`.
+    pub const PARSE: u8 = 0;
+
+    /// Report for a part that was killed after exceeding a time limit.
+    pub fn timeout(day: Day, part: u8, after: Duration) -> Self {
+        PartReport {
+            day,
+            part,
+            answer: None,
+            status: PartStatus::Timeout,
+            error: Some(format!("TIMEOUT after {after:.1?}")),
+            stats: BenchStats::from_durations(&[after]),
+        }
+    }
+
+    /// Serialize the report to a single line, as a report record.
+    pub fn to_line(&self) -> String {
+        Record::Report(self.clone()).to_line()
+    }
+
+    /// Write the report to stdout.
+    pub fn print(&self) {
+        println!("{}", self.to_line());
+    }
+
+    /// Try to read a report from a line of solution output.
+    /// Returns `None` for lines that are not a report, e.g. debug output of a solution.
+    pub fn from_line(line: &str) -> Option<Self> {
+        match Record::split_line(line).1 {
+            Some(Record::Report(report)) => Some(report),
+            _ => None,
+        }
+    }
+}
+
+/// A record in the output of a solution binary.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Record {
+    /// A part started running, so the caller can tell which part hung if it has to kill the solution.
+    Started(u8),
+    /// A part finished.
+    Report(PartReport),
+}
+
+impl Record {
+    /// Serialize the record to a single line of JSON.
+    pub fn to_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("record is always serializable.")
+    }
+
+    /// Write the record to stdout.
+    pub fn print(&self) {
+        println!("{}", self.to_line());
+    }
+
+    /// Split a line of solution output into the output in front of a record and the record itself.
+    /// Lines without a record are returned as a whole.
+    pub fn split_line(line: &str) -> (&str, Option<Self>) {
+        if !line.trim_end().ends_with('}') {
+            return (line, None);
+        }
+
+        // NOTE: records are printed on a fresh line, but output that a solution printed without a trailing
+        // newline ends up in front of them, so every `{` is tried as the start of the record.
+        let parsed = line
+            .char_indices()
+            .filter(|(_, c)| *c == '{')
+            .find_map(|(start, _)| {
+                let json = JsonValue::from_str(&line[start..]).ok()?;
+                Some((start, Record::try_from(&json).ok()?))
+            });
+
+        match parsed {
+            Some((start, record)) => (&line[..start], Some(record)),
+            None => (line, None),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Record> for JsonValue {
+    fn from(value: &Record) -> Self {
+        let (kind, mut map) = match value {
+            Record::Started(part) => {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("part".into(), JsonValue::Number(f64::from(*part)));
+                ("started", map)
+            }
+            Record::Report(report) => match JsonValue::from(report) {
+                JsonValue::Object(map) => ("report", map),
+                _ => unreachable!("part reports serialize to objects"),
+            },
+        };
+
+        map.insert("type".into(), JsonValue::String(kind.into()));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        match json.get("type").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "report" => PartReport::try_from(value).map(Record::Report),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some(x) if x == "started" => json
+                .get("part")
+                .and_then(|v| v.get::<f64>())
+                .map(|v| Record::Started(*v as u8))
+                .ok_or("Expected record.part to be a number.".into()),
+            _ => Err("Expected record.type to be `started` or `report`.".into()),
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let error = json
+            .get("error")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.error to be null or string.")?;
+
+        let stats = json
+            .get("stats")
+            .ok_or("Expected report.stats to be present.")
+            .map(BenchStats::try_from)??;
+
+        Ok(PartReport {
+            day,
+            part,
+            answer: answer.cloned(),
+            status,
+            error: error.cloned(),
+            stats,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartReport, PartStatus, Record};
+    use crate::{day, template::stats::BenchStats};
+    use std::{collections::HashMap, str::FromStr, time::Duration};
+    use tinyjson::JsonValue;
+
+    fn get_mock_report() -> PartReport {
+        PartReport {
+            day: day!(1),
+            part: 2,
+            answer: Some("42".into()),
+            status: PartStatus::Solved,
+            error: None,
+            stats: BenchStats::from_durations(&[
+                Duration::from_micros(74),
+                Duration::from_micros(75),
+            ]),
+        }
+    }
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = get_mock_report();
+        let line = report.to_line();
+        assert_eq!(PartReport::from_line(&line), Some(report));
+    }
+
+    #[test]
+    fn roundtrips_multiline_answers() {
+        let report = PartReport {
+            answer: Some("@ @ @\n( ) ms".into()),
+            ..get_mock_report()
+        };
+        let line = report.to_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(PartReport::from_line(&line), Some(report));
+    }
+
+    #[test]
+    fn handles_missing_answers() {
+        let report = PartReport {
+            answer: None,
+            status: PartStatus::Unsolved,
+            ..get_mock_report()
+        };
+        let line = report.to_line();
+        assert_eq!(PartReport::from_line(&line), Some(report));
+    }
+
+    #[test]
+    fn handles_errors() {
+        let report = PartReport {
+            answer: None,
+            status: PartStatus::Panicked,
+            error: Some("panicked at src/bin/01.rs:14:52: oops".into()),
+            ..get_mock_report()
+        };
+        let line = report.to_line();
+        assert_eq!(PartReport::from_line(&line), Some(report));
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(
+            PartReport::from_line("Part 1: 0 (74.13ns @ 100000 samples)"),
+            None
+        );
+        assert_eq!(PartReport::from_line("{ not json"), None);
+        assert_eq!(PartReport::from_line(r#"{ "day": "01" }"#), None);
+        assert_eq!(PartReport::from_line(""), None);
+    }
+
+    #[test]
+    fn prints_records_as_json_objects() {
+        let line = get_mock_report().to_line();
+        assert!(line.starts_with('{'));
+        let json = JsonValue::from_str(&line).unwrap();
+        let json = json.get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(json["type"], JsonValue::String("report".into()));
+        assert_eq!(json["answer"], JsonValue::String("42".into()));
+
+        let line = Record::Started(1).to_line();
+        assert!(line.starts_with('{'));
+        assert_eq!(Record::split_line(&line), ("", Some(Record::Started(1))));
+    }
+
+    #[test]
+    fn reads_records_after_other_output() {
+        let report = get_mock_report();
+        let line = format!("[5, 7, 3, 0]{}", report.to_line());
+
+        assert_eq!(
+            Record::split_line(&line),
+            ("[5, 7, 3, 0]", Some(Record::Report(report.clone())))
+        );
+        assert_eq!(PartReport::from_line(&report.to_line()), Some(report));
+
+        let line = format!("debug{}", Record::Started(2).to_line());
+        assert_eq!(
+            Record::split_line(&line),
+            ("debug", Some(Record::Started(2)))
+        );
+        assert_eq!(PartReport::from_line(&line), None);
+    }
+}
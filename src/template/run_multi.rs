@@ -1,40 +1,113 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io, process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    input::InputSource,
+    registry::{self, DaySolution},
+    report::PartReport,
+    runner::{print_report, RunLimits},
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
+/// Results of running a set of days.
+pub struct MultiRun {
+    /// Part reports of every scaffolded day that was run, in day order.
+    pub reports: Vec<(Day, Vec<PartReport>)>,
+    /// Benchmark timings, only present for timed runs.
+    pub timings: Option<Timings>,
+}
 
+/// Run a set of days on an input source and collect their reports.
+///
+/// Days registered in [`registry`] run in-process if possible. Solution bins are spawned instead when
+/// limits need to be enforced, days run in parallel, or the requested profile differs from the current one.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    input: &InputSource,
+    is_release: bool,
+    is_timed: bool,
+    limits: RunLimits,
+    jobs: usize,
+) -> MultiRun {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    // NOTE: limits can only be enforced on a separate process.
+    let in_process =
+        jobs == 1 && limits == RunLimits::default() && is_release != cfg!(debug_assertions);
+
+    // NOTE: solution bins are run directly, so they have to be built upfront. This also keeps compile time
+    // out of the day's time limit, and parallel invocations do not contend for the build.
+    // A failed build aborts the run, as the bins of the last successful build would run stale code otherwise.
+    if !in_process || days.iter().any(|day| registry::get(*day).is_none()) {
+        match child_commands::build_solutions(is_release) {
+            Ok(()) => {}
+            Err(Error::BuildFailed) => {
+                eprintln!("Failed to build solutions.");
+                process::exit(1);
             }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            Err(e) => {
+                eprintln!("Failed to build solutions: {e:?}");
+                process::exit(1);
+            }
+        }
+    }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut all_reports: Vec<(Day, Vec<PartReport>)> = Vec::with_capacity(days_to_run.len());
+
+    let mut collect_reports = |day: Day, reports: Vec<PartReport>| {
+        if reports.is_empty() {
+            println!("Not solved.");
+        } else {
+            let val = child_commands::timing_from_reports(&reports, day);
+            timings.push(val);
+            all_reports.push((day, reports));
+        }
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    if jobs > 1 {
+        // run days in parallel with buffered output, then replay the output in day order.
+        run_parallel(
+            &days,
+            jobs,
+            |day| {
+                child_commands::run_solution(day, input, is_timed, is_release, limits, true)
+                    .unwrap()
+            },
+            |i, output| {
+                print_day_header(days[i], i == 0);
+                output.lines.iter().for_each(OutputLine::print);
+                collect_reports(days[i], output.reports);
+            },
+        );
+    } else {
+        for (i, day) in days.iter().enumerate() {
+            print_day_header(*day, i == 0);
+            let output = match registry::get(*day).filter(|_| in_process) {
+                Some(solution) => run_in_process(solution, input, is_timed),
+                None => {
+                    child_commands::run_solution(*day, input, is_timed, is_release, limits, false)
+                        .unwrap()
+                }
+            };
+            collect_reports(*day, output.reports);
+        }
+    }
 
-    if is_timed {
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,13 +116,110 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        reports: all_reports,
+        timings,
+    }
+}
+
+/// Run a registered solution in the current process, printing the report of each part as it finishes.
+fn run_in_process(solution: &DaySolution, input: &InputSource, is_timed: bool) -> SolutionOutput {
+    let mut output = SolutionOutput::default();
+
+    let result = solution.solve(input, is_timed, |report| {
+        print_report(&report);
+        output.reports.push(report);
+    });
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+    }
+
+    output
+}
+
+fn print_day_header(day: Day, is_first: bool) {
+    if !is_first {
+        println!();
     }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Run `run` for every day on up to `jobs` threads.
+/// `on_done` is called on the calling thread with the index of each day, in order, as soon as the day and all days before it have finished.
+fn run_parallel<T: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> T + Sync,
+    mut on_done: impl FnMut(usize, T),
+) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let (next, run) = (&next, &run);
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(day) = days.get(i) else {
+                    break;
+                };
+                if tx.send((i, run(*day))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut finished: BTreeMap<usize, T> = BTreeMap::new();
+        let mut next_done = 0;
+
+        for (i, result) in rx {
+            finished.insert(i, result);
+
+            while let Some(result) = finished.remove(&next_done) {
+                on_done(next_done, result);
+                next_done += 1;
+            }
+        }
+    });
+}
+
+/// A line of output produced by a solution bin.
+pub enum OutputLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+impl OutputLine {
+    pub fn print(&self) {
+        match self {
+            OutputLine::Stdout(line) => println!("{line}"),
+            OutputLine::Stderr(line) => eprintln!("{line}"),
+        }
+    }
+}
+
+/// Part reports of a solution bin, plus its output if it was buffered.
+#[derive(Default)]
+pub struct SolutionOutput {
+    pub reports: Vec<PartReport>,
+    pub lines: Vec<OutputLine>,
 }
 
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    /// `cargo build` exited with an error, its output has been printed already.
+    BuildFailed,
     IO(io::Error),
 }
 
@@ -59,47 +229,102 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading the structured records they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, OutputLine, SolutionOutput};
+    use crate::template::{
+        get_path_for_bin,
+        input::InputSource,
+        registry,
+        report::{OutputFormat, PartReport, Record},
+        runner::{format_report, RunLimits},
+        solution_args::SolutionArgs,
+        timings::PartTiming,
+        Day, PuzzleId,
+    };
     use std::{
+        env,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        sync::{
+            mpsc::{self, RecvTimeoutError},
+            Mutex,
+        },
         thread,
+        time::Instant,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Build all solution bins without running them.
+    /// Compiler output is only printed if the build fails, so it does not get in front of the days' output.
+    /// Returns [`Error::BuildFailed`] if any bin failed to build.
+    pub fn build_solutions(is_release: bool) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        let output = Command::new("cargo").args(&args).output()?;
+        if !output.status.success() {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            return Err(Error::BuildFailed);
+        }
+
+        Ok(())
+    }
+
+    /// Path of a solution bin built by [`build_solutions`]. Bins are built into the same target directory as
+    /// the main binary that is running.
+    fn bin_path(bin_name: &str, is_release: bool) -> PathBuf {
+        let target_dir = env::current_exe()
+            .ok()
+            .and_then(|exe| Some(exe.parent()?.parent()?.to_path_buf()))
+            .unwrap_or_else(|| PathBuf::from("target"));
+
+        let profile = if is_release { "release" } else { "debug" };
+        target_dir
+            .join(profile)
+            .join(format!("{bin_name}{}", env::consts::EXE_SUFFIX))
+    }
+
+    /// Run the solution bin for a given day and collect the reports for each part.
+    /// If the day exceeds its time limit, the bin is killed and the running part is reported as timed out.
+    /// If `buffered` is set, output is collected instead of forwarded, so parallel runs do not interleave.
+    pub fn run_solution(
+        day: Day,
+        input: &InputSource,
+        is_timed: bool,
+        is_release: bool,
+        limits: RunLimits,
+        buffered: bool,
+    ) -> Result<SolutionOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(SolutionOutput::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
+        // skip days that were scaffolded after the bins were built.
+        let bin_path = bin_path(&PuzzleId::from(day).to_string(), is_release);
+        if !bin_path.exists() {
+            return Ok(SolutionOutput::default());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        // request structured output from child invocations, and mirror `--time` and the limits to them.
+        let args = SolutionArgs {
+            time: is_timed,
+            format: OutputFormat::Json,
+            input: input.clone(),
+            limits,
+            ..SolutionArgs::default()
         }
+        .to_args();
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // spawn the built bin directly with piped stdout/stderr, so killing it stops the solution itself.
+        // forward (or buffer) output to stdout/stderr while grabbing part reports.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(bin_path)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -108,87 +333,116 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let lines = Mutex::new(vec![]);
+        let emit = |line: OutputLine| {
+            if buffered {
+                lines.lock().unwrap().push(line);
+            } else {
+                line.print();
+            }
+        };
+
+        let mut reports = vec![];
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        thread::scope(|scope| -> Result<(), Error> {
+            scope.spawn(|| {
+                stderr.lines().for_each(|line| {
+                    emit(OutputLine::Stderr(line.unwrap()));
+                });
             });
-        });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+            // read stdout on a separate thread, so the day's time limit can be enforced while waiting for output.
+            let (tx, rx) = mpsc::channel();
+            scope.spawn(move || {
+                for line in stdout.lines() {
+                    if tx.send(line.unwrap()).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            let started = Instant::now();
+            let mut running = None;
+
+            loop {
+                let line = match limits.day_timeout {
+                    Some(timeout) => rx.recv_timeout(timeout.saturating_sub(started.elapsed())),
+                    None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+
+                match line {
+                    Ok(line) => {
+                        let (output, record) = Record::split_line(&line);
+                        if record.is_none() || !output.is_empty() {
+                            emit(OutputLine::Stdout(output.to_string()));
+                        }
+                        match record {
+                            Some(Record::Started(part)) => running = Some(part),
+                            Some(Record::Report(report)) => {
+                                emit(OutputLine::Stdout(format_report(&report)));
+                                reports.push(report);
+                                running = None;
+                            }
+                            None => {}
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        cmd.kill()?;
+
+                        // NOTE: a solution that hangs before its first part, e.g. while reading its input, is
+                        // reported on the day's first part.
+                        let part = running
+                            .or_else(|| registry::parts(day).first().copied())
+                            .unwrap_or(1);
+                        let report = PartReport::timeout(day, part, started.elapsed());
+                        emit(OutputLine::Stdout(format_report(&report)));
+                        reports.push(report);
+                        break;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+
+            Ok(())
+        })?;
 
-        thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(SolutionOutput {
+            reports,
+            lines: lines.into_inner().unwrap(),
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn timing_from_reports(reports: &[PartReport], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        for report in reports {
+            let Some(part) = PartTiming::from_report(report) else {
+                continue;
+            };
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            if part.is_solved() {
+                timings.total_nanos += part.nanos;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            match report.part {
+                PartReport::PARSE => timings.parse = Some(part),
+                1 => timings.part_1 = Some(part),
+                2 => timings.part_2 = Some(part),
+                _ => {}
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,53 +459,131 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::timing_from_reports;
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{
+                report::{PartReport, PartStatus},
+                stats::BenchStats,
+            },
+        };
+        use std::time::Duration;
+
+        fn report(part: u8, answer: Option<&str>, duration_nanos: u64) -> PartReport {
+            PartReport {
+                day: day!(1),
+                part,
+                answer: answer.map(Into::into),
+                status: if answer.is_some() {
+                    PartStatus::Solved
+                } else {
+                    PartStatus::Unsolved
+                },
+                error: None,
+                stats: BenchStats::from_durations(&[Duration::from_nanos(duration_nanos)]),
+            }
+        }
+
+        #[test]
+        fn collects_execution_times() {
+            let res = timing_from_reports(
+                &[report(1, Some("0"), 74), report(2, Some("10"), 74_130_000)],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.as_ref().unwrap().format(), "74.0ns");
+            assert_eq!(res.part_2.as_ref().unwrap().format(), "74.1ms");
+            assert_eq!(res.part_2.as_ref().unwrap().nanos, 74_130_000_f64);
+            assert_eq!(res.part_1.unwrap().stats.unwrap().median_nanos, 74_f64);
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn handles_patterns_in_answers() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("@ @ @ ( ) ms"), 2_000_000_000),
+                    report(2, Some("10s"), 100_000_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 2100000000_f64);
+            assert_eq!(res.part_1.unwrap().format(), "2.0s");
+            assert_eq!(res.part_2.unwrap().format(), "100.0ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_parse_times() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    PartReport {
+                        part: PartReport::PARSE,
+                        ..report(0, Some(""), 1_000)
+                    },
+                    report(1, Some("1"), 2_000),
+                    report(2, Some("2"), 3_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos, 6000_f64);
+            assert_eq!(res.parse.unwrap().nanos, 1000_f64);
+            assert_eq!(res.part_1.unwrap().format(), "2.0µs");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn handles_timed_out_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(1, Some("1"), 2_000),
+                    PartReport::timeout(day!(1), 2, Duration::from_secs(10)),
                 ],
                 day!(1),
             );
+            assert_approx_eq!(res.total_nanos, 2000_f64);
+            assert_eq!(res.part_1.unwrap().format(), "2.0µs");
+            let part_2 = res.part_2.unwrap();
+            assert_eq!(part_2.status, PartStatus::Timeout);
+            assert_eq!(part_2.format(), "TIMEOUT");
+            assert!(part_2.stats.is_none());
+        }
+
+        #[test]
+        fn handles_missing_parts() {
+            let res = timing_from_reports(&[report(1, None, 10), report(2, None, 10)], day!(1));
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::run_parallel;
+    use crate::{day, template::Day};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn runs_days_in_order() {
+        let days: Vec<Day> = (1..=6).map(|x| Day::new(x).unwrap()).collect();
+        let mut done = vec![];
+
+        run_parallel(
+            &days,
+            3,
+            |day| {
+                // NOTE: earlier days finish last.
+                thread::sleep(Duration::from_millis(u64::from(7 - day.into_inner()) * 10));
+                day
+            },
+            |i, day| done.push((i, day)),
+        );
+
+        assert_eq!(done.len(), 6);
+        assert_eq!(done[0], (0, day!(1)));
+        assert!(done
+            .iter()
+            .enumerate()
+            .all(|(i, (j, day))| i == *j && days[i] == *day));
+    }
+}
@@ -1,35 +1,260 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Once;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, process, thread};
 
+use crate::template::answers::{AnswerKind, Answers};
+use crate::template::commands::download;
+use crate::template::report::{OutputFormat, PartReport, PartStatus, Record};
+use crate::template::solution_args::SolutionArgs;
+use crate::template::stats::BenchStats;
+use crate::template::submissions::{Outcome, Submission, Submissions};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Return types accepted from solution parts.
+///
+/// Parts can return `Option<T>`, where `None` marks an unsolved part, or `Result<T, E>`, where the error is reported next to the part.
+pub trait PartResult {
+    type Answer: Display;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    /// Returns the answer, `None` for an unsolved part, or the error message of a failed part.
+    fn answer(&self) -> Result<Option<&Self::Answer>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<Option<&T>, String> {
+        Ok(self.as_ref())
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<Option<&T>, String> {
+        match self {
+            Ok(x) => Ok(Some(x)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// Exit code of a solution binary that was stopped by the `--part-timeout` watchdog.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Resource limits for solution binaries.
+///
+/// `run_multi` enforces `day_timeout` itself by killing the binary, and mirrors the other limits
+/// to the binary as command-line flags, where they are enforced by the runner.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RunLimits {
+    /// Wall-clock limit for a whole day.
+    pub day_timeout: Option<Duration>,
+    /// Wall-clock limit for a single part, including benching.
+    pub part_timeout: Option<Duration>,
+    /// Address space limit in megabytes. Only supported on Linux.
+    pub memory_limit_mb: Option<u64>,
+}
+
+/// Limit the address space of the current process if the `--memory-limit` flag is set.
+/// Allocations beyond the limit fail, which aborts the solution.
+pub fn apply_memory_limit() {
+    let Some(limit_mb) = SolutionArgs::current().limits.memory_limit_mb else {
+        return;
+    };
+
+    #[cfg(target_os = "linux")]
+    {
+        let bytes = limit_mb.saturating_mul(1024 * 1024);
+        let limit = libc::rlimit {
+            rlim_cur: bytes,
+            rlim_max: bytes,
+        };
+
+        // SAFETY: `setrlimit` only reads the passed struct, which outlives the call.
+        if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+            eprintln!("Failed to apply memory limit of {limit_mb}MB.");
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    eprintln!("Ignoring memory limit of {limit_mb}MB, only supported on Linux.");
+}
+
+/// Start a watchdog that exits the process with a timeout report if a part exceeds the `--part-timeout` limit.
+/// The watchdog is disarmed when the returned sender is dropped.
+fn start_watchdog(day: Day, part: u8, format: OutputFormat) -> Option<mpsc::Sender<()>> {
+    let timeout = SolutionArgs::current().limits.part_timeout?;
+    let (tx, rx) = mpsc::channel::<()>();
+
+    thread::spawn(move || {
+        if rx.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+            let report = PartReport::timeout(day, part, timeout);
+            match format {
+                OutputFormat::Human => print_report(&report),
+                OutputFormat::Json => report.print(),
+            }
+            process::exit(TIMEOUT_EXIT_CODE);
+        }
+    });
+
+    Some(tx)
+}
+
+/// Run the shared parse phase of a solution and return its output, so both parts can use it.
+/// Exits the process if parsing panics, as neither part can run without its output.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I, day: Day) -> T {
+    let label = part_label(PartReport::PARSE);
+    let format = SolutionArgs::current().format;
+    let is_human = format == OutputFormat::Human;
+    let _watchdog = start_watchdog(day, PartReport::PARSE, format);
+
+    match format {
+        OutputFormat::Human => print!("{label}:"),
+        OutputFormat::Json => Record::Started(PartReport::PARSE).print(),
+    }
+
+    let (result, stats) = run_timed(func, input, SolutionArgs::current().time, is_human, |_| {});
+    let report = parse_report(day, &result, stats);
+
+    match format {
+        OutputFormat::Human => print_report(&report),
+        OutputFormat::Json => report.print(),
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    result.unwrap_or_else(|_| process::exit(1))
+}
+
+/// Run the shared parse phase of a solution in-process, without printing its report.
+/// Returns `None` as output if parsing panics.
+pub fn solve_parse<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    is_timed: bool,
+) -> (Option<T>, PartReport) {
+    let (result, stats) = run_timed(func, input, is_timed, false, |_| {});
+    let report = parse_report(day, &result, stats);
+    (result.ok(), report)
+}
+
+pub fn run_part<I: Clone, R: PartResult>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
+    let part_str = part_label(part);
+    let format = SolutionArgs::current().format;
+    let is_human = format == OutputFormat::Human;
+    let watchdog = start_watchdog(day, part, format);
+
+    if format == OutputFormat::Json {
+        Record::Started(part).print();
+    }
+
+    let (result, stats) = run_timed(
+        func,
+        input,
+        SolutionArgs::current().time,
+        is_human,
+        |result| {
+            if let (true, Ok(answer)) = (is_human, result.answer()) {
+                print_result(&answer, &part_str);
+            }
+        },
+    );
+
+    let report = part_report(day, part, &result, stats);
+
+    match format {
+        OutputFormat::Human => print_report(&report),
+        OutputFormat::Json => report.print(),
+    }
+
+    // NOTE: time spent submitting does not count towards the part's time limit.
+    drop(watchdog);
+
+    if let Ok(Ok(Some(answer))) = result.as_ref().map(PartResult::answer) {
+        match submit_result(answer, day, part) {
+            Some(Ok(submission)) => {
+                println!("{ANSI_BOLD}Submission:{ANSI_RESET} {}", submission.outcome);
+            }
+            Some(Err(e)) => {
+                eprintln!("Failed to submit: {e}");
+                process::exit(1);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Run a solution part in-process and return its report, without printing or submitting it.
+pub fn solve_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+    is_timed: bool,
+) -> PartReport {
+    let (result, stats) = run_timed(func, input, is_timed, false, |_| {});
+    part_report(day, part, &result, stats)
+}
+
+fn parse_report<T>(day: Day, result: &Result<T, String>, stats: BenchStats) -> PartReport {
+    PartReport {
+        day,
+        part: PartReport::PARSE,
+        answer: None,
+        status: if result.is_ok() {
+            PartStatus::Solved
+        } else {
+            PartStatus::Panicked
+        },
+        error: result.as_ref().err().cloned(),
+        stats,
+    }
+}
+
+fn part_report<R: PartResult>(
+    day: Day,
+    part: u8,
+    result: &Result<R, String>,
+    stats: BenchStats,
+) -> PartReport {
+    let (status, answer, error) = match result.as_ref().map(PartResult::answer) {
+        Ok(Ok(Some(answer))) => (PartStatus::Solved, Some(answer.to_string()), None),
+        Ok(Ok(None)) => (PartStatus::Unsolved, None, None),
+        Ok(Err(e)) => (PartStatus::Failed, None, Some(e)),
+        Err(e) => (PartStatus::Panicked, None, Some(e.clone())),
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    PartReport {
+        day,
+        part,
+        answer,
+        status,
+        error,
+        stats,
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Benching only happens if `is_timed` is set, e.g. by the `--time` flag.
+/// If the first execution panics, the panic message is returned as an error and the part is not benched.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
+    show_progress: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (Result<T, String>, BenchStats) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -37,30 +262,101 @@ fn run_timed<I: Clone, T>(
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        catch_panic(|| func(input))
     };
     let base_time = timer.elapsed();
 
+    let Ok(result) = result else {
+        return (result, BenchStats::from_durations(&[base_time]));
+    };
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if is_timed {
+        bench(func, input, &base_time, show_progress)
     } else {
-        (base_time, 1)
+        BenchStats::from_durations(&[base_time])
     };
 
-    (result, run.0, run.1)
+    (Ok(result), stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+thread_local! {
+    /// Number of [`catch_panic`] calls running on this thread.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    /// Message and location of the last panic on this thread, recorded by the hook installed in [`catch_panic`].
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Install a panic hook that records panics of threads inside [`catch_panic`], and passes all other panics on
+/// to the previous hook. The hook is installed once, as swapping it per call races between threads.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(Cell::get) == 0 {
+                default_hook(info);
+                return;
+            }
+
+            let message = info
+                .payload()
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| info.payload().downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic payload".into());
+
+            let message = match info.location() {
+                Some(location) => format!("panicked at {location}: {message}"),
+                None => format!("panicked: {message}"),
+            };
+
+            LAST_PANIC.with(|x| *x.borrow_mut() = Some(message));
+        }));
+    });
+}
+
+/// Run a function, converting a panic into an error that contains the panic message and location.
+/// The default panic output is suppressed while the function runs.
+pub(crate) fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    install_panic_hook();
+
+    CATCHING.with(|x| x.set(x.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING.with(|x| x.set(x.get() - 1));
+
+    result.map_err(|_| {
+        LAST_PANIC
+            .with(|x| x.borrow_mut().take())
+            .unwrap_or_else(|| "panicked".into())
+    })
+}
+
+/// Bench a solution part. Runs a number of untimed warmup iterations first so caches and CPU frequency settle.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    show_progress: bool,
+) -> BenchStats {
+    if show_progress {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 100);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,99 +367,179 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_durations(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Print a part report in the human-readable format.
+pub(crate) fn print_report(report: &PartReport) {
+    print!("\r");
+    println!("{}", format_report(report));
+}
+
+/// Format a part report in the human-readable format.
+pub(crate) fn format_report(report: &PartReport) -> String {
+    let label = part_label(report.part);
+    let stats = format_stats(&report.stats);
+
+    match (&report.error, report.part, &report.answer) {
+        (Some(error), _, _) => format!("{label}: ✖ {ANSI_ITALIC}{error}{ANSI_RESET}{stats}"),
+        (None, PartReport::PARSE, _) => format!("{label}:{stats}"),
+        (None, _, Some(answer)) if answer.contains('\n') => {
+            format!("{label}: ▼ {stats}\n{answer}")
+        }
+        (None, _, Some(answer)) => format!("{label}: {ANSI_BOLD}{answer}{ANSI_RESET}{stats}"),
+        (None, _, None) => format!("{label}: ✖             "),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// Label used to print a part, e.g. `Part 1`. The parse phase is labeled `Parse`.
+pub(crate) fn part_label(part: u8) -> String {
+    if part == PartReport::PARSE {
+        "Parse".into()
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!("Part {part}")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+pub(crate) fn format_stats(stats: &BenchStats) -> String {
+    let mean = stats.mean();
+
+    if stats.samples <= 1 {
+        return format!(" ({mean:.1?})");
+    }
+
+    let mut str = format!(
+        " ({mean:.1?} @ {} samples, median {:.1?}, p95 {:.1?}, ±{:.1?}",
+        stats.samples,
+        stats.median(),
+        stats.p95(),
+        stats.stddev()
+    );
+
+    if stats.outliers > 0 {
+        str += &format!(", {} outliers", stats.outliers);
+    }
+
+    str += ")";
+    str
+}
 
+/// Print the answer of a part while it is benched. The line is finished by [`print_report`] once the part is done.
+fn print_result<T: Display>(result: &Option<T>, part: &str) {
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
-        }
+        Some(result) if result.to_string().contains('\n') => print!("{part}: ▼ "),
+        Some(result) => print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+        None => print!("{part}: ✖"),
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
+/// Try to submit the part of the solution selected with `--submit` if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
+///  2. a session cookie is configured.
+///  3. the answer is not known to be wrong from earlier submissions.
+///
+/// The response is logged to the day's submissions. A correct answer is pinned in the answer registry, and the
+/// puzzle description is downloaded again.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) -> Option<Result<Submission, String>> {
+    if SolutionArgs::current().submit != Some(part) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    Some(submit(&result.to_string(), day, part))
+}
+
+fn submit(answer: &str, day: Day, part: u8) -> Result<Submission, String> {
+    let submissions = Submissions::read_from_file(day)
+        .map_err(|e| format!("could not read {}: {e}", Submissions::get_path(day)))?;
+
+    submissions
+        .check(part, answer)
+        .map_err(|reason| format!("not submitting, {reason}"))?;
+
+    let client = aoc_client::AocClient::from_env().map_err(|e| e.to_string())?;
+
+    println!("Submitting result...");
+    let response = client
+        .submit(day, part, answer)
+        .map_err(|e| e.to_string())?;
+    println!("{response}");
+
+    let submission = Submission::new(part, answer, Outcome::from_response(&response));
+
+    if let Err(e) = Submissions::append_file(day, &submission) {
+        eprintln!("Failed to log submission: {e}");
+    }
+
+    if submission.outcome == Outcome::Correct {
+        pin_answer(answer, day, part);
+
+        // NOTE: an accepted answer unlocks the next part of the puzzle description.
+        if let Err(e) = download::refresh_puzzle(&client, day) {
+            eprintln!("Failed to refresh the puzzle: {e}");
+        }
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    Ok(submission)
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+/// Store a correct answer in the answer registry and mark it as confirmed. Replaces an answer that was pinned
+/// before without being confirmed, e.g. with `cargo verify --pin`.
+fn pin_answer(answer: &str, day: Day, part: u8) {
+    let mut answers = match Answers::read_from_file(day) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to read answers for day {day}: {e}");
+            return;
+        }
     };
 
-    if part_submit != part {
-        return None;
+    let input = answers.get(AnswerKind::Input);
+    if answers.is_confirmed(part) && input.get(part).is_some_and(|x| x == answer) {
+        return;
+    }
+
+    answers.confirm(part, answer);
+    if let Err(e) = answers.store_file(day) {
+        eprintln!("Failed to store answers for day {day}: {e}");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{catch_panic, PartResult};
+
+    #[test]
+    fn catches_panics_with_location() {
+        let result: Result<(), String> = catch_panic(|| panic!("oops"));
+        let message = result.unwrap_err();
+        assert!(message.starts_with("panicked at src/template/runner.rs:"));
+        assert!(message.ends_with(": oops"));
+    }
+
+    #[test]
+    fn catches_panics_per_thread() {
+        let handles: Vec<_> = (0..8)
+            .map(|i| std::thread::spawn(move || catch_panic(|| panic!("thread {i}")).unwrap_err()))
+            .collect();
+
+        for (i, handle) in handles.into_iter().enumerate() {
+            assert!(handle.join().unwrap().ends_with(&format!(": thread {i}")));
+        }
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn passes_through_results() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn reads_answers_from_options_and_results() {
+        assert_eq!(Some(1).answer(), Ok(Some(&1)));
+        assert_eq!(None::<u32>.answer(), Ok(None));
+        assert_eq!(Ok::<u32, String>(1).answer(), Ok(Some(&1)));
+        assert_eq!(
+            Err::<u32, String>("bad input".into()).answer(),
+            Err("bad input".into())
+        );
+    }
 }
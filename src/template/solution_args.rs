@@ -0,0 +1,187 @@
+/// Flags that commands pass on to solution binaries, e.g. `cargo run --bin 2024-01 -- --time --format json`.
+///
+/// This is the single definition of these flags: commands build them with [`SolutionArgs::to_args`], and the
+/// runner reads them back in the solution binary with [`SolutionArgs::current`].
+use std::{env, ffi::OsString, process, sync::OnceLock, time::Duration};
+
+use crate::template::{input::InputSource, report::OutputFormat, runner::RunLimits};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SolutionArgs {
+    /// Bench every part, `--time`.
+    pub time: bool,
+    /// Submit the answer of a part, `--submit <part>`.
+    pub submit: Option<u8>,
+    /// `--format <human|json>`.
+    pub format: OutputFormat,
+    /// `--example` or `--input <path>`.
+    pub input: InputSource,
+    /// `--part-timeout <seconds>` and `--memory-limit <mb>`. The day timeout is enforced by the caller.
+    pub limits: RunLimits,
+}
+
+static CURRENT: OnceLock<SolutionArgs> = OnceLock::new();
+
+impl SolutionArgs {
+    /// The flags the running solution binary was started with. Exits the process if they are invalid.
+    pub fn current() -> &'static Self {
+        CURRENT.get_or_init(|| {
+            Self::parse(env::args_os().skip(1).collect()).unwrap_or_else(|e| {
+                eprintln!("Error: {e}");
+                eprintln!("Run `cargo solve --help` for the supported options.");
+                process::exit(1);
+            })
+        })
+    }
+
+    pub fn parse(args: Vec<OsString>) -> Result<Self, String> {
+        let mut args = pico_args::Arguments::from_vec(args);
+        let to_string = |e: pico_args::Error| e.to_string();
+
+        let is_example = args.contains("--example");
+        let path: Option<String> = args.opt_value_from_str("--input").map_err(to_string)?;
+
+        let parsed = SolutionArgs {
+            time: args.contains("--time"),
+            submit: args
+                .opt_value_from_fn("--submit", parse_part)
+                .map_err(to_string)?,
+            format: args
+                .opt_value_from_str("--format")
+                .map_err(to_string)?
+                .unwrap_or_default(),
+            input: InputSource::from_flags(is_example, path.as_deref())?,
+            limits: RunLimits {
+                day_timeout: None,
+                part_timeout: args
+                    .opt_value_from_fn("--part-timeout", parse_seconds)
+                    .map_err(to_string)?,
+                memory_limit_mb: args
+                    .opt_value_from_str("--memory-limit")
+                    .map_err(to_string)?,
+            },
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            return Err(format!("unknown argument(s): {remaining:?}"));
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.time {
+            args.push("--time".into());
+        }
+
+        if let Some(part) = self.submit {
+            args.extend(["--submit".into(), part.to_string()]);
+        }
+
+        if self.format == OutputFormat::Json {
+            args.extend(["--format".into(), "json".into()]);
+        }
+
+        match &self.input {
+            InputSource::Input => {}
+            InputSource::Example => args.push("--example".into()),
+            InputSource::Path(path) => args.extend(["--input".into(), path.display().to_string()]),
+            InputSource::Stdin => args.extend(["--input".into(), "-".into()]),
+        }
+
+        if let Some(timeout) = self.limits.part_timeout {
+            args.extend(["--part-timeout".into(), timeout.as_secs_f64().to_string()]);
+        }
+
+        if let Some(memory_limit) = self.limits.memory_limit_mb {
+            args.extend(["--memory-limit".into(), memory_limit.to_string()]);
+        }
+
+        args
+    }
+}
+
+/// Parse the number of a part, i.e. `1` or `2`.
+pub fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("expecting part `1` or `2`, got `{s}`")),
+    }
+}
+
+/// Parse a positive number of seconds, e.g. `2.5`.
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|x| x.is_finite() && *x > 0.0)
+        .map(Duration::from_secs_f64)
+        .ok_or_else(|| format!("expecting a positive number of seconds, got `{s}`"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::SolutionArgs;
+    use crate::template::{input::InputSource, report::OutputFormat, runner::RunLimits};
+    use std::time::Duration;
+
+    fn parse(args: &[&str]) -> Result<SolutionArgs, String> {
+        SolutionArgs::parse(args.iter().map(Into::into).collect())
+    }
+
+    #[test]
+    fn defaults_to_puzzle_input() {
+        assert_eq!(parse(&[]), Ok(SolutionArgs::default()));
+        assert_eq!(parse(&["--time"]).unwrap().input, InputSource::Input);
+    }
+
+    #[test]
+    fn reads_input_flags() {
+        assert_eq!(parse(&["--example"]).unwrap().input, InputSource::Example);
+        assert_eq!(parse(&["--input", "-"]).unwrap().input, InputSource::Stdin);
+        assert_eq!(
+            parse(&["--input", "other.txt"]).unwrap().input,
+            InputSource::Path("other.txt".into())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_flags() {
+        assert!(parse(&["--input"]).is_err());
+        assert!(parse(&["--input", "other.txt", "--example"]).is_err());
+        assert!(parse(&["--submit", "3"]).is_err());
+        assert!(parse(&["--part-timeout", "-1"]).is_err());
+        assert!(parse(&["--times"]).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for input in [
+            InputSource::Input,
+            InputSource::Example,
+            InputSource::Stdin,
+            InputSource::Path("other.txt".into()),
+        ] {
+            let args = SolutionArgs {
+                time: true,
+                submit: Some(2),
+                format: OutputFormat::Json,
+                input,
+                limits: RunLimits {
+                    day_timeout: None,
+                    part_timeout: Some(Duration::from_millis(1500)),
+                    memory_limit_mb: Some(512),
+                },
+            };
+
+            let strings = args.to_args();
+            let strings: Vec<&str> = strings.iter().map(String::as_str).collect();
+            assert_eq!(parse(&strings), Ok(args));
+        }
+    }
+}
@@ -0,0 +1,212 @@
+/// Summary statistics over a set of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Multiplier of the interquartile range used to flag outliers (Tukey's fences).
+const OUTLIER_IQR_FACTOR: f64 = 1.5;
+
+/// Summary of all samples collected while benching a single part.
+///
+/// Outliers are flagged with Tukey's fences and excluded from `mean_nanos` and `stddev_nanos`.
+/// All other values are computed over the full set of samples.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub outliers: u128,
+    pub mean_nanos: f64,
+    pub median_nanos: f64,
+    pub min_nanos: f64,
+    pub max_nanos: f64,
+    pub p95_nanos: f64,
+    pub stddev_nanos: f64,
+}
+
+impl BenchStats {
+    /// Compute statistics for a non-empty set of samples.
+    pub fn from_durations(durations: &[Duration]) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = durations.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        if nanos.is_empty() {
+            return Self::default();
+        }
+
+        let q1 = percentile(&nanos, 25.0);
+        let q3 = percentile(&nanos, 75.0);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - OUTLIER_IQR_FACTOR * iqr, q3 + OUTLIER_IQR_FACTOR * iqr);
+
+        let kept: Vec<f64> = nanos
+            .iter()
+            .copied()
+            .filter(|x| *x >= lower && *x <= upper)
+            .collect();
+
+        let mean_nanos = mean(&kept);
+
+        Self {
+            samples: nanos.len() as u128,
+            outliers: (nanos.len() - kept.len()) as u128,
+            mean_nanos,
+            median_nanos: percentile(&nanos, 50.0),
+            min_nanos: nanos[0],
+            max_nanos: nanos[nanos.len() - 1],
+            p95_nanos: percentile(&nanos, 95.0),
+            stddev_nanos: stddev(&kept, mean_nanos),
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        to_duration(self.mean_nanos)
+    }
+
+    pub fn median(&self) -> Duration {
+        to_duration(self.median_nanos)
+    }
+
+    pub fn p95(&self) -> Duration {
+        to_duration(self.p95_nanos)
+    }
+
+    pub fn stddev(&self) -> Duration {
+        to_duration(self.stddev_nanos)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/// Linear interpolation between the closest ranks of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - rank.floor())
+}
+
+fn mean(values: &[f64]) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let len = values.len() as f64;
+    values.iter().sum::<f64>() / len
+}
+
+fn stddev(values: &[f64], mean: f64) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let len = values.len() as f64;
+    (values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (len - 1.0)).sqrt()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+            map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        }
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95_nanos));
+        map.insert("stddev_nanos".into(), JsonValue::Number(value.stddev_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+            mean_nanos: number("mean_nanos")?,
+            median_nanos: number("median_nanos")?,
+            min_nanos: number("min_nanos")?,
+            max_nanos: number("max_nanos")?,
+            p95_nanos: number("p95_nanos")?,
+            stddev_nanos: number("stddev_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchStats;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn durations(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_basic_stats() {
+        let stats = BenchStats::from_durations(&durations(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.min_nanos, 1.0);
+        assert_eq!(stats.max_nanos, 5.0);
+        assert_eq!(stats.median_nanos, 3.0);
+        assert_eq!(stats.mean_nanos, 3.0);
+        assert!((stats.p95_nanos - 4.8).abs() < 1e-9);
+        assert!((stats.stddev_nanos - 2.5_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn interpolates_even_medians() {
+        let stats = BenchStats::from_durations(&durations(&[1, 2, 3, 4]));
+        assert_eq!(stats.median_nanos, 2.5);
+    }
+
+    #[test]
+    fn rejects_outliers_from_mean() {
+        let stats = BenchStats::from_durations(&durations(&[10, 12, 10, 12, 11, 11, 1000]));
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max_nanos, 1000.0);
+        assert_eq!(stats.median_nanos, 11.0);
+        assert_eq!(stats.mean_nanos, 11.0);
+    }
+
+    #[test]
+    fn handles_single_samples() {
+        let stats = BenchStats::from_durations(&durations(&[42]));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median_nanos, 42.0);
+        assert_eq!(stats.p95_nanos, 42.0);
+        assert_eq!(stats.stddev_nanos, 0.0);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = BenchStats::from_durations(&durations(&[5, 1, 4, 2, 3]));
+        let json = JsonValue::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+}
@@ -0,0 +1,443 @@
+/// Log of answer submissions, stored as one JSON object per line in `data/<year>/submissions/NN.jsonl`.
+///
+/// The website's response to every submission is classified and logged. Before an answer is submitted,
+/// the log is checked so answers that are known to be wrong, or lie outside a known too-high/too-low
+/// bracket, are not sent again.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+fn submissions_dir() -> PathBuf {
+    Year::current().data_dir().join("submissions")
+}
+
+/// How the website responded to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The part was solved before, the answer was not checked.
+    AlreadySolved,
+    /// An answer was submitted too recently. Contains how long to wait before trying again, if known.
+    RateLimited(Option<Duration>),
+    /// The response could not be classified.
+    Unknown,
+}
+
+impl Outcome {
+    /// Classify the response to a submission, as returned by `AocClient::submit`.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Outcome::Correct
+        } else if response.contains("That's not the right answer") {
+            if response.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if response.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if response.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else if response.contains("You gave an answer too recently") {
+            Outcome::RateLimited(parse_wait(response))
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    /// Whether the answer is known to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Wrong => "wrong",
+            Outcome::AlreadySolved => "already_solved",
+            Outcome::RateLimited(_) => "rate_limited",
+            Outcome::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+            Outcome::RateLimited(Some(wait)) => {
+                write!(f, "rate-limited, try again in {}s", wait.as_secs())
+            }
+            Outcome::RateLimited(None) => write!(f, "rate-limited"),
+            Outcome::Unknown => write!(f, "unknown response"),
+        }
+    }
+}
+
+/// Parse the wait time from a response like "You have 1m 30s left to wait."
+fn parse_wait(response: &str) -> Option<Duration> {
+    let end = response.find("left to wait")?;
+    let start = response[..end].rfind("You have")? + "You have".len();
+
+    let mut secs = 0;
+    let mut found = false;
+    for token in response[start..end].split_whitespace() {
+        let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        secs += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+        found = true;
+    }
+
+    found.then(|| Duration::from_secs(secs))
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+impl Submission {
+    /// Record a submission that was answered just now.
+    pub fn new(part: u8, answer: &str, outcome: Outcome) -> Self {
+        Submission {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+            part,
+            answer: answer.into(),
+            outcome,
+        }
+    }
+}
+
+/// All submissions of a day, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub entries: Vec<Submission>,
+}
+
+impl Submissions {
+    #[must_use]
+    pub fn get_path(day: Day) -> String {
+        submissions_dir()
+            .join(format!("{day}.jsonl"))
+            .display()
+            .to_string()
+    }
+
+    /// Read the submissions of a day. If nothing was submitted yet, returns an empty log.
+    pub fn read_from_file(day: Day) -> Result<Self, String> {
+        match fs::read_to_string(Self::get_path(day)) {
+            Ok(content) => Submissions::try_from(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Append a submission to the log of a day.
+    pub fn append_file(day: Day, submission: &Submission) -> Result<(), io::Error> {
+        let dir = submissions_dir();
+        if !dir.exists() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let line = JsonValue::from(submission)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::get_path(day))?;
+
+        writeln!(file, "{line}")
+    }
+
+    fn of_part(&self, part: u8) -> impl Iterator<Item = &Submission> {
+        self.entries.iter().filter(move |x| x.part == part)
+    }
+
+    /// The answer of a part that the website accepted, if any.
+    pub fn correct_answer(&self, part: u8) -> Option<&String> {
+        self.of_part(part)
+            .find(|x| x.outcome == Outcome::Correct)
+            .map(|x| &x.answer)
+    }
+
+    /// Whether the website accepted an answer of a part, or reported it as solved already.
+    pub fn is_solved(&self, part: u8) -> bool {
+        self.of_part(part)
+            .any(|x| matches!(x.outcome, Outcome::Correct | Outcome::AlreadySolved))
+    }
+
+    /// Check whether an answer is worth submitting. Returns the reason if it is not.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), String> {
+        if let Some(correct) = self.correct_answer(part) {
+            return Err(format!(
+                "part {part} was already solved with answer {correct}."
+            ));
+        }
+
+        if let Some(previous) = self
+            .of_part(part)
+            .find(|x| x.answer == answer && x.outcome.is_wrong())
+        {
+            return Err(format!(
+                "{answer} was already submitted and is {}.",
+                previous.outcome
+            ));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let numeric = |outcome: Outcome| {
+            self.of_part(part)
+                .filter(move |x| x.outcome == outcome)
+                .filter_map(|x| x.answer.parse::<i128>().ok())
+        };
+
+        if let Some(too_high) = numeric(Outcome::TooHigh).filter(|x| value >= *x).min() {
+            return Err(format!("{answer} is too high, {too_high} already was."));
+        }
+
+        if let Some(too_low) = numeric(Outcome::TooLow).filter(|x| value <= *x).max() {
+            return Err(format!("{answer} is too low, {too_low} already was."));
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.key().into()),
+        );
+        map.insert(
+            "wait".into(),
+            match value.outcome {
+                #[allow(clippy::cast_precision_loss)]
+                Outcome::RateLimited(Some(wait)) => JsonValue::Number(wait.as_secs() as f64),
+                _ => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let wait = json
+            .get("wait")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| Duration::from_secs(*v as u64));
+
+        let outcome = match json.get("outcome").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "correct" => Outcome::Correct,
+            Some(x) if x == "too_high" => Outcome::TooHigh,
+            Some(x) if x == "too_low" => Outcome::TooLow,
+            Some(x) if x == "wrong" => Outcome::Wrong,
+            Some(x) if x == "already_solved" => Outcome::AlreadySolved,
+            Some(x) if x == "rate_limited" => Outcome::RateLimited(wait),
+            Some(x) if x == "unknown" => Outcome::Unknown,
+            _ => return Err("Expected submission.outcome to be a known outcome.".into()),
+        };
+
+        Ok(Submission {
+            timestamp,
+            part,
+            answer: answer.clone(),
+            outcome,
+        })
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let entries = value
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                JsonValue::from_str(line)
+                    .map_err(|_| "not valid JSON.".to_string())
+                    .and_then(|json| Submission::try_from(&json))
+                    .map_err(|e| format!("line {}: {e}", i + 1))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Submissions { entries })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Outcome, Submission, Submissions};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn submission(part: u8, answer: &str, outcome: Outcome) -> Submission {
+        Submission {
+            timestamp: 1_733_000_000,
+            part,
+            answer: answer.into(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn classifies_responses() {
+        let cases = [
+            ("That's the right answer! You are one gold star closer.", Outcome::Correct),
+            ("That's not the right answer; your answer is too high. If you're stuck, ...", Outcome::TooHigh),
+            ("That's not the right answer; your answer is too low. Please wait one minute.", Outcome::TooLow),
+            ("That's not the right answer. If you're stuck, make sure you're using the full input data.", Outcome::Wrong),
+            ("You don't seem to be solving the right level. Did you already complete it?", Outcome::AlreadySolved),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 30s left to wait.",
+                Outcome::RateLimited(Some(Duration::from_secs(90))),
+            ),
+            ("You gave an answer too recently.", Outcome::RateLimited(None)),
+            ("Something else entirely.", Outcome::Unknown),
+        ];
+
+        for (response, expected) in cases {
+            assert_eq!(Outcome::from_response(response), expected, "{response}");
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = Submissions {
+            entries: vec![
+                submission(1, "abc", Outcome::Wrong),
+                submission(1, "100", Outcome::TooHigh),
+                submission(1, "80", Outcome::TooHigh),
+                submission(1, "20", Outcome::TooLow),
+                submission(1, "30", Outcome::RateLimited(None)),
+                submission(2, "7", Outcome::Correct),
+            ],
+        };
+
+        assert!(submissions.check(1, "abc").is_err());
+        assert!(submissions.check(1, "abd").is_ok());
+        assert_eq!(
+            submissions.check(1, "90").unwrap_err(),
+            "90 is too high, 80 already was."
+        );
+        assert!(submissions.check(1, "80").is_err());
+        assert!(submissions.check(1, "20").is_err());
+        assert!(submissions.check(1, "-5").is_err());
+        assert!(submissions.check(1, "30").is_ok());
+        assert!(submissions.check(1, "79").is_ok());
+        assert!(submissions.check(2, "8").is_err());
+        assert_eq!(submissions.correct_answer(1), None);
+        assert_eq!(submissions.correct_answer(2), Some(&"7".to_string()));
+    }
+
+    #[test]
+    fn counts_already_solved_parts() {
+        let submissions = Submissions {
+            entries: vec![
+                submission(1, "5", Outcome::AlreadySolved),
+                submission(2, "6", Outcome::Wrong),
+            ],
+        };
+
+        assert!(submissions.is_solved(1));
+        assert!(!submissions.is_solved(2));
+        assert_eq!(submissions.correct_answer(1), None);
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let entries = vec![
+            submission(1, "42", Outcome::TooLow),
+            submission(1, "43", Outcome::RateLimited(Some(Duration::from_secs(45)))),
+            submission(2, "a b", Outcome::Correct),
+        ];
+
+        let content = entries
+            .iter()
+            .map(|x| JsonValue::from(x).stringify().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let submissions = Submissions::try_from(content).unwrap();
+        assert_eq!(submissions.entries, entries);
+    }
+}
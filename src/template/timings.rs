@@ -1,19 +1,116 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    report::{PartReport, PartStatus},
+    stats::BenchStats,
+    Day, Year,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+fn timings_file_path() -> PathBuf {
+    Year::current().data_dir().join("timings.json")
+}
+
+fn baselines_dir() -> PathBuf {
+    Year::current().data_dir().join("baselines")
+}
+
+/// Format a duration in nanos like the benchmark output, e.g. `74.1µs`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Benchmark result of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Either [`PartStatus::Solved`] or [`PartStatus::Timeout`].
+    pub status: PartStatus,
+    /// Mean duration in nanoseconds. For timed out parts, the time until the part was stopped.
+    pub nanos: f64,
+    /// Number of samples. `0` for timings migrated from files that did not record it.
+    pub samples: u128,
+    /// Full statistics. Absent for timings migrated from files that did not record them.
+    pub stats: Option<BenchStats>,
+}
+
+impl PartTiming {
+    /// Timing of a solved or timed out part. Returns `None` for other reports.
+    pub fn from_report(report: &PartReport) -> Option<Self> {
+        match report.status {
+            PartStatus::Solved | PartStatus::Timeout => Some(PartTiming {
+                status: report.status,
+                nanos: report.stats.mean_nanos,
+                samples: report.stats.samples,
+                stats: (report.status == PartStatus::Solved).then(|| report.stats.clone()),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.status == PartStatus::Solved
+    }
+
+    /// Median duration in nanoseconds. Falls back to the mean for timings stored without stats.
+    pub fn median_nanos(&self) -> f64 {
+        self.stats.as_ref().map_or(self.nanos, |s| s.median_nanos)
+    }
+
+    /// Human-readable mean duration, e.g. `74.1µs`, or `TIMEOUT` for timed out parts.
+    pub fn format(&self) -> String {
+        if self.is_solved() {
+            format_nanos(self.nanos)
+        } else {
+            "TIMEOUT".into()
+        }
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Timing of a part, or of the parse phase for [`PartReport::PARSE`].
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            PartReport::PARSE => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Stats of a part, or of the parse phase for [`PartReport::PARSE`].
+    pub fn stats(&self, part: u8) -> Option<&BenchStats> {
+        self.part(part)?.stats.as_ref()
+    }
+
+    /// Sum up the median durations of all solved parts as nanos.
+    pub fn median_nanos(&self) -> f64 {
+        [&self.parse, &self.part_1, &self.part_2]
+            .iter()
+            .flat_map(|x| x.iter())
+            .filter(|x| x.is_solved())
+            .map(PartTiming::median_nanos)
+            .sum()
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -25,18 +122,66 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let path = timings_file_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(timings_file_path())
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
+    /// Path of a named baseline. Names may only contain alphanumeric characters, `-`, `_` and `.`.
+    pub fn get_baseline_path(name: &str) -> Result<String, String> {
+        let is_valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+        if is_valid {
+            Ok(baselines_dir()
+                .join(format!("{name}.json"))
+                .display()
+                .to_string())
+        } else {
+            Err(format!(
+                "invalid baseline name `{name}`, expecting alphanumeric characters, `-`, `_` or `.`."
+            ))
+        }
+    }
+
+    /// Dehydrate timings to a named baseline.
+    pub fn store_baseline(&self, name: &str) -> Result<(), String> {
+        let path = Self::get_baseline_path(name)?;
+
+        let dir = baselines_dir();
+        if !dir.exists() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path).map_err(|e| e.to_string())?;
+        json.format_to(&mut file).map_err(|e| e.to_string())
+    }
+
+    /// Rehydrate timings from a named baseline. If not present, returns empty timings.
+    pub fn read_baseline(name: &str) -> Result<Self, String> {
+        match fs::read_to_string(Self::get_baseline_path(name)?) {
+            Ok(content) => Timings::try_from(content),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
     /// Merge two sets of timings, overwriting `self` with `other` if present.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
@@ -60,10 +205,21 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+    /// Sum up the median durations of all benched parts as millis.
+    /// Falls back to the total for days stored without stats.
+    pub fn total_median_millis(&self) -> f64 {
+        self.data.iter().map(Timing::median_nanos).sum::<f64>() / 1_000_000_f64
+    }
+
+    /// Whether all `parts` of a day have been timed and solved. Parts that timed out are not complete, so they
+    /// are benched again.
+    pub fn is_day_complete(&self, day: Day, parts: &[u8]) -> bool {
+        self.data.iter().any(|t| {
+            t.day == day
+                && parts
+                    .iter()
+                    .all(|part| t.part(*part).is_some_and(PartTiming::is_solved))
+        })
     }
 }
 
@@ -107,28 +263,21 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
-impl From<&Timing> for JsonValue {
-    fn from(value: &Timing) -> Self {
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
         );
-
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
                 None => JsonValue::Null,
             },
         );
@@ -137,6 +286,114 @@ impl From<&Timing> for JsonValue {
     }
 }
 
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected part.status to be a string.")?
+            .parse()?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected part.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        Ok(PartTiming {
+            status,
+            nanos,
+            samples,
+            stats,
+        })
+    }
+}
+
+/// Read a part timing written before timings were numeric, i.e. a formatted duration like `"74.1µs"` or
+/// `"TIMEOUT"`, along with its optional stats.
+fn migrate_part_timing(formatted: &str, stats: Option<BenchStats>) -> Result<PartTiming, String> {
+    if formatted == "TIMEOUT" {
+        return Ok(PartTiming {
+            status: PartStatus::Timeout,
+            nanos: 0.0,
+            samples: 0,
+            stats: None,
+        });
+    }
+
+    let nanos = match &stats {
+        Some(stats) => stats.mean_nanos,
+        None => parse_duration_nanos(formatted)
+            .ok_or(format!("Expected `{formatted}` to be a duration."))?,
+    };
+
+    Ok(PartTiming {
+        status: PartStatus::Solved,
+        nanos,
+        samples: stats.as_ref().map_or(0, |x| x.samples),
+        stats,
+    })
+}
+
+/// Parse a duration formatted with [`Duration`]'s `Debug` implementation, e.g. `74.13ns` or `1.2µs`, to nanos.
+fn parse_duration_nanos(s: &str) -> Option<f64> {
+    let units = [
+        ("ns", 1.0),
+        ("µs", 1e3),
+        ("us", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+    ];
+
+    units.iter().find_map(|(unit, factor)| {
+        let value = s.strip_suffix(unit)?.parse::<f64>().ok()?;
+        Some(value * factor)
+    })
+}
+
+impl From<&Timing> for JsonValue {
+    fn from(value: &Timing) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+
+        for (key, part) in [
+            ("parse", &value.parse),
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+        ] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -151,25 +408,45 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: stats were stored next to the formatted durations before timings were numeric.
+        let read_legacy_stats = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => BenchStats::try_from(v).map(Some),
+        };
+
+        let read_part = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(JsonValue::String(formatted)) => {
+                migrate_part_timing(formatted, read_legacy_stats(&format!("{key}_stats"))?)
+                    .map(Some)
+            }
+            Some(v) => PartTiming::try_from(v)
+                .map(Some)
+                .map_err(|e| format!("timing.{key}: {e}")),
+        };
+
+        let parse = match json.get("parse") {
+            Some(_) => read_part("parse")?,
+            None => read_legacy_stats("parse_stats")?.map(|stats| PartTiming {
+                status: PartStatus::Solved,
+                nanos: stats.mean_nanos,
+                samples: stats.samples,
+                stats: Some(stats),
+            }),
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1: read_part("part_1")?,
+            part_2: read_part("part_2")?,
             total_nanos,
         })
     }
@@ -179,28 +456,40 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, template::report::PartStatus};
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
+
+    pub(super) fn solved(nanos: f64) -> PartTiming {
+        PartTiming {
+            status: PartStatus::Solved,
+            nanos,
+            samples: 1,
+            stats: None,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(solved(10e6)),
+                    part_2: Some(solved(20e6)),
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(solved(30e6)),
+                    part_2: Some(solved(40e6)),
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(solved(40e6)),
                     part_2: None,
                     total_nanos: 4e+10,
                 },
@@ -209,20 +498,58 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use super::{get_mock_timings, solved};
+        use crate::{
+            day,
+            template::{report::PartStatus, timings::Timings},
+        };
+        use tinyjson::JsonValue;
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "parse": null, "part_1": { "status": "solved", "nanos": 1000000, "samples": 1, "stats": null }, "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(solved(1e6)));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn migrates_formatted_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.1µs", "part_2": "TIMEOUT", "total_nanos": 74100 }, { "day": "02", "part_1": "1.5s", "part_2": "12.0ns", "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            assert_eq!(part_1.status, PartStatus::Solved);
+            assert!((part_1.nanos - 74_100.0).abs() < 1e-6);
+            assert_eq!(part_1.samples, 0);
+            assert_eq!(part_1.format(), "74.1µs");
+            assert_eq!(
+                timings.data[0].part_2.as_ref().unwrap().status,
+                PartStatus::Timeout
+            );
+            assert_eq!(timings.data[0].parse, None);
+
+            assert_eq!(timings.data[1].part_1.as_ref().unwrap().nanos, 1.5e9);
+            assert_eq!(timings.data[1].part_2.as_ref().unwrap().nanos, 12.0);
+        }
+
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::try_from(timings.clone())
+                .unwrap()
+                .stringify()
+                .unwrap();
+            let read = Timings::try_from(json).unwrap();
+            assert_eq!(read.data.len(), 3);
+            assert_eq!(read.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(read.data[2].part_2, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -269,9 +596,13 @@ mod tests {
     }
 
     mod is_day_complete {
+        use super::solved;
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                report::PartStatus,
+                timings::{PartTiming, Timing, Timings},
+            },
         };
 
         #[test]
@@ -279,13 +610,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(solved(1e6)),
+                    part_2: Some(solved(2e6)),
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1), &[1, 2]), true);
         }
 
         #[test]
@@ -293,13 +625,15 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(solved(1e6)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1), &[1, 2]), false);
+            assert_eq!(timings.is_day_complete(day!(1), &[1]), true);
         }
 
         #[test]
@@ -307,13 +641,33 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1), &[1, 2]), false);
+        }
+
+        #[test]
+        fn handles_timed_out_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: Some(solved(1e6)),
+                    part_2: Some(PartTiming {
+                        status: PartStatus::Timeout,
+                        ..solved(2e9)
+                    }),
+                    total_nanos: 1_000_000_f64,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1), &[1, 2]), false);
+            assert_eq!(timings.is_day_complete(day!(1), &[1]), true);
         }
     }
 
@@ -331,6 +685,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
@@ -351,6 +706,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
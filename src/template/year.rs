@@ -0,0 +1,334 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Once, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::template::{config::Config, day::SERVER_UTC_OFFSET, Day};
+
+/// Environment variable that holds the year commands and solutions work on.
+const YEAR_ENV_VAR: &str = "AOC_YEAR";
+
+/// The year of the first event.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year with an event of 12 days.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// A year of Advent of Code (i.e. 2015 or later).
+///
+/// Every year keeps its data in `data/<year>` and its solutions in `src/bin/<year>-NN.rs`.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year with an event,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The year that commands work on: set with `--year` or `AOC_YEAR`, defaulting to the latest event
+    /// that has started. Solutions and their tests always use the year of their binary.
+    pub fn current() -> Self {
+        Self::of_running_bin()
+            .or_else(|| env::var(YEAR_ENV_VAR).ok().and_then(|x| x.parse().ok()))
+            .unwrap_or_else(Self::latest)
+    }
+
+    /// Year of the running executable if it is a solution, e.g. `2024-01`, or the tests of one, e.g.
+    /// `2024_01-<hash>`.
+    fn of_running_bin() -> Option<Self> {
+        static YEAR: OnceLock<Option<Year>> = OnceLock::new();
+
+        *YEAR.get_or_init(|| {
+            let exe = env::current_exe().ok()?;
+            let name = exe.file_name()?.to_str()?.get(..7)?.replace('_', "-");
+            Self::from_bin_name(&name)
+        })
+    }
+
+    /// Make this the current year of the process and of the solutions it spawns.
+    pub fn set_current(self) {
+        env::set_var(YEAR_ENV_VAR, self.to_string());
+    }
+
+    /// The latest event that has started.
+    pub fn latest() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs())
+            .unwrap_or_default();
+
+        Self::latest_at(secs)
+    }
+
+    /// The latest event that has started at a time, as seconds since the Unix epoch.
+    /// Events start at midnight in the website's time zone, not in UTC.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn latest_at(secs: u64) -> Self {
+        let secs = secs as i64 + i64::from(SERVER_UTC_OFFSET) * 3600;
+
+        // Civil date from days since the epoch, see https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = secs.div_euclid(86_400) + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Self((if month == 12 { year } else { year - 1 }) as u16)
+    }
+
+    /// Read the year from the name of a solution binary, e.g. `2024-01`.
+    pub const fn from_bin_name(name: &str) -> Option<Self> {
+        let bytes = name.as_bytes();
+        if bytes.len() != 7 || bytes[4] != b'-' {
+            return None;
+        }
+
+        let mut year = 0;
+        let mut i = 0;
+        while i < 4 {
+            if !bytes[i].is_ascii_digit() {
+                return None;
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Number of days of the event. Configured in the `[years.<year>]` section of `aoc.toml`:
+    ///
+    /// ```toml
+    /// [years.2025]
+    /// days = 12
+    /// ```
+    ///
+    /// Defaults to 25 days, and to 12 days from 2025 on.
+    pub fn days(self) -> u8 {
+        static WARNING: Once = Once::new();
+
+        let configured = Config::cached()
+            .map_err(String::from)
+            .and_then(|config| self.configured_days(config));
+
+        match configured {
+            Ok(Some(days)) => days,
+            Ok(None) => self.default_days(),
+            Err(e) => {
+                WARNING.call_once(|| {
+                    eprintln!("Failed to read the length of the {self} event from `aoc.toml`: {e}");
+                });
+                self.default_days()
+            }
+        }
+    }
+
+    /// The last day of the event.
+    pub fn last_day(self) -> Day {
+        // NOTE: the number of days is verified to be in range 1 to 25.
+        Day::__new_unchecked(self.days())
+    }
+
+    fn default_days(self) -> u8 {
+        if self.0 >= FIRST_SHORT_YEAR {
+            12
+        } else {
+            25
+        }
+    }
+
+    fn configured_days(self, config: &Config) -> Result<Option<u8>, String> {
+        let Some(section) = config
+            .section("years")
+            .and_then(|x| x.get(self.to_string()))
+        else {
+            return Ok(None);
+        };
+
+        let days = section
+            .as_table()
+            .ok_or_else(|| format!("Expected years.{self} to be a table."))?
+            .get("days")
+            .map(|v| {
+                v.as_integer()
+                    .and_then(|x| u8::try_from(x).ok())
+                    .filter(|x| Day::new(*x).is_some())
+                    .ok_or_else(|| {
+                        format!("Expected years.{self}.days to be a number between 1 and 25.")
+                    })
+            })
+            .transpose()?;
+
+        Ok(days)
+    }
+
+    /// Directory that holds the inputs, examples, answers and timings of the year.
+    #[must_use]
+    pub fn data_dir(self) -> PathBuf {
+        PathBuf::from("data").join(self.to_string())
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of advent, from {FIRST_YEAR} on")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A puzzle of a specific year.
+///
+/// # Display
+/// This value displays as the name of its solution binary, e.g. `2024-08`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub fn new(year: Year, day: Day) -> Self {
+        PuzzleId { year, day }
+    }
+}
+
+/// The puzzle of a day in the current year.
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        PuzzleId::new(Year::current(), day)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+impl FromStr for PuzzleId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s
+            .split_once('-')
+            .ok_or_else(|| format!("expecting a puzzle like `2024-01`, got `{s}`"))?;
+
+        Ok(PuzzleId {
+            year: year.parse().map_err(|e: YearFromStrError| e.to_string())?,
+            day: day
+                .parse()
+                .map_err(|e: crate::template::DayFromStrError| e.to_string())?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PuzzleId, Year};
+    use crate::{day, template::config::Config};
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().unwrap().into_inner(), 2024);
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_years_from_bin_names() {
+        assert_eq!(Year::from_bin_name("2023-07"), Year::new(2023));
+        assert_eq!(Year::from_bin_name("07"), None);
+        assert_eq!(Year::from_bin_name("advent_of_code"), None);
+        assert_eq!(Year::from_bin_name("1999-01"), None);
+    }
+
+    #[test]
+    fn reads_event_lengths() {
+        let config = Config::try_from("[years.2023]\ndays = 12\n").unwrap();
+        let year = |x| Year::new(x).unwrap();
+
+        assert_eq!(year(2023).configured_days(&config), Ok(Some(12)));
+        assert_eq!(year(2024).configured_days(&config), Ok(None));
+        assert_eq!(year(2024).default_days(), 25);
+        assert_eq!(year(2025).default_days(), 12);
+
+        let config = Config::try_from("[years.2023]\ndays = 26\n").unwrap();
+        assert!(year(2023).configured_days(&config).is_err());
+    }
+
+    #[test]
+    fn starts_events_at_midnight_utc_minus_5() {
+        // 2024-12-01 00:00:00 UTC and 05:00:00 UTC.
+        let midnight_utc = 1_733_011_200;
+        let year = |x| Year::new(x).unwrap();
+
+        assert_eq!(Year::latest_at(midnight_utc), year(2023));
+        assert_eq!(Year::latest_at(midnight_utc + 5 * 3600 - 1), year(2023));
+        assert_eq!(Year::latest_at(midnight_utc + 5 * 3600), year(2024));
+        // 2025-01-01 03:00:00 UTC, still december of 2024 in the website's time zone.
+        assert_eq!(Year::latest_at(1_735_700_400), year(2024));
+    }
+
+    #[test]
+    fn roundtrips_puzzle_ids() {
+        let id = PuzzleId::new(Year::new(2022).unwrap(), day!(8));
+        assert_eq!(id.to_string(), "2022-08");
+        assert_eq!("2022-08".parse::<PuzzleId>(), Ok(id));
+        assert!("2022-26".parse::<PuzzleId>().is_err());
+    }
+}